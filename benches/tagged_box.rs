@@ -1,8 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use tagged_box::{tagged_box, TaggableContainer, TaggableInner, TaggedBox};
+use tagged_box::{tagged_box, TaggableInner, TaggedBox};
 
 tagged_box! {
-    #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
     struct Container, enum Item {
         SingleTuple(usize),
         ManyTuple(usize, usize, f32, usize),
@@ -14,6 +14,15 @@ tagged_box! {
     }
 }
 
+tagged_box! {
+    #[derive(Clone)]
+    struct ArrayContainer, enum Array {
+        Usizes([usize; 100]),
+        Big([usize; 2048]),
+    }
+}
+
+#[allow(clippy::legacy_numeric_constants)]
 fn default_impl(c: &mut Criterion) {
     c.bench_function("Unit into_tagged_box", |b| {
         b.iter(|| black_box(Item::Unit).into_tagged_box());
@@ -34,12 +43,48 @@ fn default_impl(c: &mut Criterion) {
         });
     })
     .bench_function("TaggedBox::new", |b| {
-        b.iter(|| TaggedBox::<[usize; 100]>::new(black_box([100usize; 100]), 0))
+        b.iter(|| unsafe { TaggedBox::<Array>::new(black_box([100usize; 100]), 0) })
     })
     .bench_function("Box::new", |b| {
         b.iter(|| Box::new(black_box([100usize; 100])))
     });
 }
 
-criterion_group!(benches, default_impl);
+/// Clones by rebuilding the enum and boxing it again, which is what `TaggedBox::clone` does for
+/// enums that don't derive `Clone`
+fn clone_reboxed<T: TaggableInner + Clone>(tagged: &TaggedBox<T>) -> TaggedBox<T> {
+    let mut output = None;
+    unsafe {
+        T::ref_from_tagged_box(tagged, |this| output = Some(this.clone()));
+    }
+
+    output.unwrap().into_tagged_box()
+}
+
+fn clone(c: &mut Criterion) {
+    let array = unsafe { TaggedBox::<Array>::new([100usize; 100], 0) };
+    let big = unsafe { TaggedBox::<Array>::new([100usize; 2048], 1) };
+    let tuple = Item::ManyTuple(100, 200, 50.100005, 300).into_tagged_box();
+
+    c.bench_function("Array TaggedBox::clone", |b| {
+        b.iter(|| black_box(&array).clone());
+    })
+    .bench_function("Array reboxed clone", |b| {
+        b.iter(|| clone_reboxed(black_box(&array)));
+    })
+    .bench_function("Big Array TaggedBox::clone", |b| {
+        b.iter(|| black_box(&big).clone());
+    })
+    .bench_function("Big Array reboxed clone", |b| {
+        b.iter(|| clone_reboxed(black_box(&big)));
+    })
+    .bench_function("Multiple element Tuple TaggedBox::clone", |b| {
+        b.iter(|| black_box(&tuple).clone());
+    })
+    .bench_function("Multiple element Tuple reboxed clone", |b| {
+        b.iter(|| clone_reboxed(black_box(&tuple)));
+    });
+}
+
+criterion_group!(benches, default_impl, clone);
 criterion_main!(benches);
@@ -1,5 +1,29 @@
+use std::{env, process::Command};
+
 fn main() {
-    if let Ok(var) = dbg!(std::env::var("TAGGED_BOX_RESERVED_WIDTH")) {
+    for width in 48..=63 {
+        println!(
+            "cargo:rustc-check-cfg=cfg(tagged_box_reserve_{}bits)",
+            width
+        );
+    }
+    println!("cargo:rustc-check-cfg=cfg(tagged_box_strict_provenance)");
+
+    // The strict provenance methods of pointers were stabilized in 1.84, older compilers use a polyfill
+    if matches!(rustc_minor_version(), Some(minor) if minor >= 84) {
+        println!("cargo:rustc-cfg=tagged_box_strict_provenance");
+    }
+
+    // 32bit targets use every bit of their pointers, so the reserved width doesn't apply to them
+    if env::var("CARGO_CFG_TARGET_POINTER_WIDTH").as_deref() == Ok("32") {
+        if env::var("TAGGED_BOX_RESERVED_WIDTH").is_ok() {
+            println!("cargo:warning=`TAGGED_BOX_RESERVED_WIDTH` is ignored on 32bit targets");
+        }
+
+        return;
+    }
+
+    if let Ok(var) = dbg!(env::var("TAGGED_BOX_RESERVED_WIDTH")) {
         match &*var {
             "63bits" => println!("cargo:rustc-cfg=tagged_box_reserve_63bits"),
             "62bits" => println!("cargo:rustc-cfg=tagged_box_reserve_62bits"),
@@ -26,3 +50,17 @@ fn main() {
         println!("cargo:rustc-cfg=tagged_box_reserve_60bits");
     }
 }
+
+/// Fetches the minor version of the compiler, `84` for `rustc 1.84.0`
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+
+    pieces.next()?.parse().ok()
+}
@@ -0,0 +1,78 @@
+use core::alloc::Layout;
+
+/// An allocator that [`TaggedBox`] can make its heap allocations with
+///
+/// A `TaggedBox` is only a single tagged pointer, so there's no room to store an instance of an
+/// allocator within it. Allocators are instead used through associated functions, allocators with
+/// state such as arenas can keep it in a `static` or a thread local
+///
+/// As a result, every box using an allocator type allocates from the same place, so one type can
+/// only ever reach a single arena. Separate arenas, like one per VM when several run in the same
+/// process, need an allocator type per arena, see the [allocator docs of `tagged_box!`]
+///
+/// ```rust
+/// use core::{
+///     alloc::Layout,
+///     sync::atomic::{AtomicUsize, Ordering},
+/// };
+/// use tagged_box::{Global, RawAllocator};
+///
+/// static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+///
+/// /// An allocator that counts how many allocations are currently alive
+/// struct Counted;
+///
+/// unsafe impl RawAllocator for Counted {
+///     unsafe fn alloc(layout: Layout) -> *mut u8 {
+///         ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+///         Global::alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+///         ALLOCATIONS.fetch_sub(1, Ordering::Relaxed);
+///         Global::dealloc(ptr, layout)
+///     }
+/// }
+/// ```
+///
+/// # Safety
+///
+/// `alloc` must return either null or a pointer to a block of memory that fits `layout`,
+/// which stays valid until it's passed to `dealloc`
+///
+/// [`TaggedBox`]: crate::TaggedBox
+/// [allocator docs of `tagged_box!`]: macro.tagged_box.html#allocators
+pub unsafe trait RawAllocator {
+    /// Allocates a block of memory that fits `layout`, returning null if the allocation failed
+    ///
+    /// # Safety
+    ///
+    /// `layout` must not have a size of zero
+    unsafe fn alloc(layout: Layout) -> *mut u8;
+
+    /// Deallocates the block of memory at `ptr`
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `alloc` of the same allocator with the same `layout`,
+    /// and must not have been deallocated already
+    unsafe fn dealloc(ptr: *mut u8, layout: Layout);
+}
+
+/// The global allocator, which is the default allocator of [`TaggedBox`]
+///
+/// [`TaggedBox`]: crate::TaggedBox
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Global;
+
+unsafe impl RawAllocator for Global {
+    #[inline]
+    unsafe fn alloc(layout: Layout) -> *mut u8 {
+        alloc::alloc::alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+        alloc::alloc::dealloc(ptr, layout)
+    }
+}
@@ -0,0 +1,455 @@
+use crate::{
+    discriminant::{Discriminant, DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    provenance,
+    tagged_pointer::TaggedPointer,
+};
+use core::{
+    fmt,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// A [`TaggedPointer`] that can be safely shared between threads, where the pointer and its
+/// [`Discriminant`] are always loaded and updated together
+///
+/// This makes it usable in lock-free structures, where the discriminant can be used as an
+/// ABA counter or to store state bits next to the pointer
+///
+/// ```rust
+/// use core::sync::atomic::Ordering;
+/// use tagged_box::{AtomicTaggedPointer, TaggedPointer};
+///
+/// let integer = 10u32;
+/// let atomic = AtomicTaggedPointer::new(TaggedPointer::<0, 2>::from_ptr(&integer, 0));
+///
+/// // Set the lowest bit of the discriminant without touching the pointer
+/// let previous = atomic.fetch_or_tag(0b01, Ordering::AcqRel);
+/// assert_eq!(previous.discriminant(), 0);
+///
+/// let current = atomic.load(Ordering::Acquire);
+/// assert_eq!(current.discriminant(), 0b01);
+/// assert_eq!(current.as_ptr::<u32>(), &integer as *const u32);
+/// ```
+///
+/// Internally the tagged pointer is stored in an [`AtomicPtr`] instead of an `AtomicU64`, so the
+/// pointer keeps its provenance and it works on every target that has atomic pointers
+///
+/// [`TaggedPointer`]: crate::TaggedPointer
+/// [`Discriminant`]: crate::Discriminant
+/// [`AtomicPtr`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html
+#[repr(transparent)]
+pub struct AtomicTaggedPointer<
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+> {
+    /// The raw tagged pointer, only ever holds pointers that came from a `TaggedPointer`
+    tagged_ptr: AtomicPtr<()>,
+}
+
+impl<const FREE_BITS: u32, const LOW_BITS: u32> AtomicTaggedPointer<FREE_BITS, LOW_BITS> {
+    /// Creates a new atomic tagged pointer
+    #[inline]
+    pub const fn new(tagged: TaggedPointer<FREE_BITS, LOW_BITS>) -> Self {
+        Self {
+            tagged_ptr: AtomicPtr::new(tagged.as_raw_ptr()),
+        }
+    }
+
+    /// Consumes the atomic, returning the contained tagged pointer
+    #[inline]
+    pub fn into_inner(self) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        // Safety: Only pointers from `TaggedPointer`s are ever stored
+        unsafe { TaggedPointer::from_raw_ptr(self.tagged_ptr.into_inner()) }
+    }
+
+    /// Loads the tagged pointer, see [`AtomicPtr::load`]
+    ///
+    /// [`AtomicPtr::load`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html#method.load
+    #[inline]
+    pub fn load(&self, order: Ordering) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        // Safety: Only pointers from `TaggedPointer`s are ever stored
+        unsafe { TaggedPointer::from_raw_ptr(self.tagged_ptr.load(order)) }
+    }
+
+    /// Stores a tagged pointer, see [`AtomicPtr::store`]
+    ///
+    /// [`AtomicPtr::store`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html#method.store
+    #[inline]
+    pub fn store(&self, tagged: TaggedPointer<FREE_BITS, LOW_BITS>, order: Ordering) {
+        self.tagged_ptr.store(tagged.as_raw_ptr(), order);
+    }
+
+    /// Stores a tagged pointer, returning the previous one, see [`AtomicPtr::swap`]
+    ///
+    /// [`AtomicPtr::swap`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html#method.swap
+    #[inline]
+    pub fn swap(
+        &self,
+        tagged: TaggedPointer<FREE_BITS, LOW_BITS>,
+        order: Ordering,
+    ) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        let previous = self.tagged_ptr.swap(tagged.as_raw_ptr(), order);
+
+        // Safety: Only pointers from `TaggedPointer`s are ever stored
+        unsafe { TaggedPointer::from_raw_ptr(previous) }
+    }
+
+    /// Stores `new` if the current tagged pointer is equal to `current`, comparing both the pointer
+    /// and the discriminant. See [`AtomicPtr::compare_exchange`]
+    ///
+    /// ```rust
+    /// use core::sync::atomic::Ordering;
+    /// use tagged_box::{AtomicTaggedPointer, TaggedPointer};
+    ///
+    /// let integer = 10u32;
+    /// let first = TaggedPointer::<0, 2>::from_ptr(&integer, 0);
+    /// let atomic = AtomicTaggedPointer::new(first);
+    ///
+    /// // The pointer is the same, but the discriminant has changed
+    /// let second = TaggedPointer::<0, 2>::from_ptr(&integer, 1);
+    /// assert!(atomic.compare_exchange(second, first, Ordering::AcqRel, Ordering::Acquire).is_err());
+    ///
+    /// assert_eq!(
+    ///     atomic.compare_exchange(first, second, Ordering::AcqRel, Ordering::Acquire),
+    ///     Ok(first),
+    /// );
+    /// assert_eq!(atomic.load(Ordering::Acquire), second);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the current tagged pointer if it wasn't equal to `current`
+    ///
+    /// [`AtomicPtr::compare_exchange`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html#method.compare_exchange
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: TaggedPointer<FREE_BITS, LOW_BITS>,
+        new: TaggedPointer<FREE_BITS, LOW_BITS>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<TaggedPointer<FREE_BITS, LOW_BITS>, TaggedPointer<FREE_BITS, LOW_BITS>> {
+        let result = self.tagged_ptr.compare_exchange(
+            current.as_raw_ptr(),
+            new.as_raw_ptr(),
+            success,
+            failure,
+        );
+
+        // Safety: Only pointers from `TaggedPointer`s are ever stored
+        unsafe {
+            result
+                .map(|ptr| TaggedPointer::from_raw_ptr(ptr))
+                .map_err(|ptr| TaggedPointer::from_raw_ptr(ptr))
+        }
+    }
+
+    /// Stores `new` if the current tagged pointer is equal to `current`, but is allowed to spuriously
+    /// fail. See [`AtomicPtr::compare_exchange_weak`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the current tagged pointer if it wasn't equal to `current` or if the exchange
+    /// spuriously failed
+    ///
+    /// [`AtomicPtr::compare_exchange_weak`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html#method.compare_exchange_weak
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: TaggedPointer<FREE_BITS, LOW_BITS>,
+        new: TaggedPointer<FREE_BITS, LOW_BITS>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<TaggedPointer<FREE_BITS, LOW_BITS>, TaggedPointer<FREE_BITS, LOW_BITS>> {
+        let result = self.tagged_ptr.compare_exchange_weak(
+            current.as_raw_ptr(),
+            new.as_raw_ptr(),
+            success,
+            failure,
+        );
+
+        // Safety: Only pointers from `TaggedPointer`s are ever stored
+        unsafe {
+            result
+                .map(|ptr| TaggedPointer::from_raw_ptr(ptr))
+                .map_err(|ptr| TaggedPointer::from_raw_ptr(ptr))
+        }
+    }
+
+    /// Repeatedly applies `update` to the current tagged pointer until it's successfully stored or
+    /// `update` returns `None`, see [`AtomicPtr::fetch_update`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the current tagged pointer if `update` returned `None`
+    ///
+    /// [`AtomicPtr::fetch_update`]: https://doc.rust-lang.org/core/sync/atomic/struct.AtomicPtr.html#method.fetch_update
+    #[inline]
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut update: F,
+    ) -> Result<TaggedPointer<FREE_BITS, LOW_BITS>, TaggedPointer<FREE_BITS, LOW_BITS>>
+    where
+        F: FnMut(TaggedPointer<FREE_BITS, LOW_BITS>) -> Option<TaggedPointer<FREE_BITS, LOW_BITS>>,
+    {
+        let result = self.tagged_ptr.fetch_update(set_order, fetch_order, |ptr| {
+            // Safety: Only pointers from `TaggedPointer`s are ever stored
+            update(unsafe { TaggedPointer::from_raw_ptr(ptr) }).map(TaggedPointer::as_raw_ptr)
+        });
+
+        // Safety: Only pointers from `TaggedPointer`s are ever stored
+        unsafe {
+            result
+                .map(|ptr| TaggedPointer::from_raw_ptr(ptr))
+                .map_err(|ptr| TaggedPointer::from_raw_ptr(ptr))
+        }
+    }
+
+    /// Performs a bitwise or of `tag` and the current discriminant, leaving the pointer unchanged
+    /// and returning the previous tagged pointer
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `tag` is greater than [`MAX_DISCRIMINANT`]
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    #[inline]
+    pub fn fetch_or_tag(
+        &self,
+        tag: Discriminant,
+        order: Ordering,
+    ) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        self.fetch_map_tag(order, |discriminant| discriminant | tag, tag)
+    }
+
+    /// Performs a bitwise and of `tag` and the current discriminant, leaving the pointer unchanged
+    /// and returning the previous tagged pointer
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `tag` is greater than [`MAX_DISCRIMINANT`]
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    #[inline]
+    pub fn fetch_and_tag(
+        &self,
+        tag: Discriminant,
+        order: Ordering,
+    ) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        self.fetch_map_tag(order, |discriminant| discriminant & tag, tag)
+    }
+
+    /// Replaces the current discriminant with `map(discriminant)`, leaving the pointer unchanged
+    #[inline]
+    #[allow(clippy::absurd_extreme_comparisons)]
+    fn fetch_map_tag(
+        &self,
+        order: Ordering,
+        map: impl Fn(Discriminant) -> Discriminant,
+        tag: Discriminant,
+    ) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        debug_assert!(
+            tag <= TaggedPointer::<FREE_BITS, LOW_BITS>::MAX_DISCRIMINANT,
+            "Attempted to use a tag of {} while the max value is {}",
+            tag,
+            TaggedPointer::<FREE_BITS, LOW_BITS>::MAX_DISCRIMINANT,
+        );
+
+        let update = |ptr| {
+            let tagged = provenance::map_addr(ptr, |addr| {
+                let addr = addr as u64;
+                let discriminant = TaggedPointer::<FREE_BITS, LOW_BITS>::fetch_discriminant(addr);
+                let pointer = TaggedPointer::<FREE_BITS, LOW_BITS>::strip_discriminant(addr);
+
+                TaggedPointer::<FREE_BITS, LOW_BITS>::store_discriminant(pointer, map(discriminant))
+                    as usize
+            });
+
+            Some(tagged)
+        };
+
+        let previous = match self
+            .tagged_ptr
+            .fetch_update(order, fetch_ordering(order), update)
+        {
+            Ok(previous) | Err(previous) => previous,
+        };
+
+        // Safety: Only pointers from `TaggedPointer`s are ever stored, and the discriminants of them
+        // are only ever replaced by discriminants in range of the same `TaggedPointer`
+        unsafe { TaggedPointer::from_raw_ptr(previous) }
+    }
+}
+
+/// The strongest ordering that can be used to load the current value for an update with `order`
+#[inline]
+fn fetch_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        order => order,
+    }
+}
+
+impl<const FREE_BITS: u32, const LOW_BITS: u32> From<TaggedPointer<FREE_BITS, LOW_BITS>>
+    for AtomicTaggedPointer<FREE_BITS, LOW_BITS>
+{
+    #[inline]
+    fn from(tagged: TaggedPointer<FREE_BITS, LOW_BITS>) -> Self {
+        Self::new(tagged)
+    }
+}
+
+impl<const FREE_BITS: u32, const LOW_BITS: u32> fmt::Debug
+    for AtomicTaggedPointer<FREE_BITS, LOW_BITS>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::SeqCst), f)
+    }
+}
+
+impl<const FREE_BITS: u32, const LOW_BITS: u32> fmt::Pointer
+    for AtomicTaggedPointer<FREE_BITS, LOW_BITS>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.load(Ordering::SeqCst), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::{sync::Arc, vec::Vec};
+    use std::thread;
+
+    #[test]
+    fn load_store_swap() {
+        let (first, second) = (10u32, 20u32);
+        let first_ptr = TaggedPointer::<0, 2>::from_ptr(&first, 1);
+        let second_ptr = TaggedPointer::<0, 2>::from_ptr(&second, 2);
+
+        let atomic = AtomicTaggedPointer::new(first_ptr);
+        assert_eq!(atomic.load(Ordering::Acquire), first_ptr);
+
+        atomic.store(second_ptr, Ordering::Release);
+        assert_eq!(atomic.load(Ordering::Acquire), second_ptr);
+
+        assert_eq!(atomic.swap(first_ptr, Ordering::AcqRel), second_ptr);
+        assert_eq!(atomic.into_inner(), first_ptr);
+
+        unsafe {
+            assert_eq!(first_ptr.as_ref::<u32>(), &10);
+            assert_eq!(second_ptr.as_ref::<u32>(), &20);
+        }
+    }
+
+    #[test]
+    fn compare_exchange() {
+        let integer = 10u32;
+        let zero = TaggedPointer::<0, 2>::from_ptr(&integer, 0);
+        let one = TaggedPointer::<0, 2>::from_ptr(&integer, 1);
+
+        let atomic = AtomicTaggedPointer::new(zero);
+        assert_eq!(
+            atomic.compare_exchange(one, zero, Ordering::AcqRel, Ordering::Acquire),
+            Err(zero),
+        );
+        assert_eq!(
+            atomic.compare_exchange(zero, one, Ordering::AcqRel, Ordering::Acquire),
+            Ok(zero),
+        );
+
+        let mut current = atomic.load(Ordering::Acquire);
+        loop {
+            match atomic.compare_exchange_weak(current, zero, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(previous) => {
+                    assert_eq!(previous, one);
+                    break;
+                }
+                Err(actual) => current = actual,
+            }
+        }
+        assert_eq!(atomic.load(Ordering::Acquire), zero);
+    }
+
+    #[test]
+    fn fetch_update() {
+        let integer = 10u32;
+        let atomic = AtomicTaggedPointer::new(TaggedPointer::<0, 2>::from_ptr(&integer, 0));
+
+        let previous = atomic.fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+            Some(TaggedPointer::from_ptr(
+                current.as_ptr::<u32>(),
+                current.discriminant() + 1,
+            ))
+        });
+        assert_eq!(previous.map(|ptr| ptr.discriminant()), Ok(0));
+        assert_eq!(atomic.load(Ordering::Acquire).discriminant(), 1);
+
+        let unchanged = atomic.fetch_update(Ordering::AcqRel, Ordering::Acquire, |_| None);
+        assert_eq!(unchanged.map_err(|ptr| ptr.discriminant()), Err(1));
+    }
+
+    #[test]
+    fn tag_operations() {
+        let integer = 10u32;
+        let atomic = AtomicTaggedPointer::new(TaggedPointer::<0, 2>::from_ptr(&integer, 0));
+
+        let previous = atomic.fetch_or_tag(0b01, Ordering::AcqRel);
+        assert_eq!(previous.discriminant(), 0);
+        let previous = atomic.fetch_or_tag(0b10, Ordering::AcqRel);
+        assert_eq!(previous.discriminant(), 0b01);
+
+        let current = atomic.load(Ordering::Acquire);
+        assert_eq!(current.discriminant(), 0b11);
+        assert_eq!(current.as_ptr::<u32>(), &integer as *const u32);
+
+        let previous = atomic.fetch_and_tag(0b10, Ordering::AcqRel);
+        assert_eq!(previous.discriminant(), 0b11);
+
+        let current = atomic.load(Ordering::Acquire);
+        assert_eq!(current.discriminant(), 0b10);
+        unsafe {
+            assert_eq!(current.as_ref::<u32>(), &10);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn concurrent_counter() {
+        static INTEGER: u32 = 10;
+
+        let atomic = Arc::new(AtomicTaggedPointer::<0, 2>::new(TaggedPointer::from_ptr(
+            &INTEGER, 0,
+        )));
+
+        // Use the discriminant as a counter that wraps around, like an ABA counter would
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let atomic = Arc::clone(&atomic);
+
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        atomic
+                            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                                let next = (current.discriminant() + 1) % 4;
+                                Some(TaggedPointer::from_ptr(current.as_ptr::<u32>(), next))
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let current = atomic.load(Ordering::Acquire);
+        assert_eq!(current.discriminant(), 0);
+        assert_eq!(current.as_ptr::<u32>(), &INTEGER as *const u32);
+    }
+}
@@ -1,7 +1,23 @@
-//! Stores variables that change upon different reserved pointer widths, as set by features
+//! Stores variables that change upon different reserved pointer widths, as set by `TAGGED_BOX_RESERVED_WIDTH`  
+//! These are the values used by [`TaggedPointer`] and [`TaggedBox`] when no `FREE_BITS` or `LOW_BITS` are given,
+//! see [`TaggedPointer::MAX_DISCRIMINANT`] and friends for the values of other widths
+//!
+//! On 32bit targets every bit of a pointer is used for addresses, so `TAGGED_BOX_RESERVED_WIDTH` is ignored
+//! and the discriminant is stored in the lower bits of pointers instead, see [`LOW_DISCRIMINANT_BITS`]
+//!
+//! [`TaggedPointer`]: crate::TaggedPointer
+//! [`TaggedBox`]: crate::TaggedBox
+//! [`TaggedPointer::MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+//! [`LOW_DISCRIMINANT_BITS`]: crate::discriminant::LOW_DISCRIMINANT_BITS
 
 pub use variables::*;
 
+/// A discriminant stored in a [`TaggedPointer`], wide enough to hold the discriminant of any
+/// number of free bits
+///
+/// [`TaggedPointer`]: crate::TaggedPointer
+pub type Discriminant = u16;
+
 /// Macro to help generate documentation  
 /// Note: Any actual expressions that cannot be coerced into an `ident` should be wrapped
 /// in a combination of `stringify!` and `concat!`
@@ -28,15 +44,11 @@ macro_rules! doc {
 
 /// Macro to help generate the discriminant variables for every reserved pointer width
 macro_rules! generate_discriminants {
-    ($([ $feature:ident, $discrim:ty, $max:expr, $ptr_width:expr, $free_bits:expr ]),*) => {
+    ($([ $feature:ident, $max:expr, $ptr_width:expr, $free_bits:expr ]),*) => {
         $(
-            #[cfg($feature)]
+            #[cfg(all(target_pointer_width = "64", $feature))]
             mod variables {
-                doc! {
-                    "A discriminant stored in a [`TaggedPointer`], represented by a `", stringify!($discrim),
-                    "` for the `", stringify!($feature), "` feature\n\n[`TaggedPointer`]: crate::TaggedPointer";
-                    pub type Discriminant = $discrim;
-                }
+                use super::Discriminant;
 
                 doc! {
                     "The maximum allowed value of a discriminant, which for the `", stringify!($feature), "` feature is ", $max;
@@ -45,44 +57,73 @@ macro_rules! generate_discriminants {
 
                 doc! {
                     "The maximum allowed value of a pointer, which for the `", stringify!($feature), "` feature is `2 ^ ", $ptr_width, "`";
-                    pub const MAX_POINTER_VALUE: u64 = u64::max_value() >> $free_bits;
+                    pub const MAX_POINTER_VALUE: u64 = u64::MAX >> $free_bits;
                 }
 
                 doc! {
                     "The reserved width of a pointer, which for the `", stringify!($feature), "` feature is ", $ptr_width, " bits";
-                    pub const POINTER_WIDTH: u64 = $ptr_width;
+                    pub const POINTER_WIDTH: u32 = $ptr_width;
                 }
 
                 doc! {
                     "A mask to remove the upper free bits of a tagged pointer, which for the `", stringify!($feature),
                     "` feature is `usize::MAX >> ", $free_bits, "`";
-                    pub const DISCRIMINANT_MASK: u64 = u64::max_value() >> $free_bits;
+                    pub const DISCRIMINANT_MASK: u64 = u64::MAX >> $free_bits;
                 }
 
                 doc! {
-                    "The total number of bits reserved for a discriminant, which for the `", stringify!($feature), "`feature is `", $free_bits, "`";
-                    pub const DISCRIMINANT_BITS: u64 = $free_bits;
+                    "The total number of bits reserved for a discriminant, which for the `", stringify!($feature), "` feature is `", $free_bits, "`";
+                    pub const DISCRIMINANT_BITS: u32 = $free_bits;
                 }
+
+                /// The number of lower bits reserved for a discriminant, which is always `0` on 64bit targets
+                pub const LOW_DISCRIMINANT_BITS: u32 = 0;
             }
         )*
     };
 }
 
 generate_discriminants! {
-    [tagged_box_reserve_63bits, u8,  1,     63, 1 ],
-    [tagged_box_reserve_62bits, u8,  3,     62, 2 ],
-    [tagged_box_reserve_61bits, u8,  7,     61, 3 ],
-    [tagged_box_reserve_60bits, u8,  15,    60, 4 ],
-    [tagged_box_reserve_59bits, u8,  31,    59, 5 ],
-    [tagged_box_reserve_58bits, u8,  63,    58, 6 ],
-    [tagged_box_reserve_57bits, u8,  127,   57, 7 ],
-    [tagged_box_reserve_56bits, u8,  255,   56, 8 ],
-    [tagged_box_reserve_55bits, u16, 511,   55, 9 ],
-    [tagged_box_reserve_54bits, u16, 1023,  54, 10],
-    [tagged_box_reserve_53bits, u16, 2047,  53, 11],
-    [tagged_box_reserve_52bits, u16, 4068,  52, 12],
-    [tagged_box_reserve_51bits, u16, 8191,  51, 13],
-    [tagged_box_reserve_50bits, u16, 16383, 50, 14],
-    [tagged_box_reserve_49bits, u16, 32767, 49, 15],
-    [tagged_box_reserve_48bits, u16, 65535, 48, 16]
+    [tagged_box_reserve_63bits, 1,     63, 1 ],
+    [tagged_box_reserve_62bits, 3,     62, 2 ],
+    [tagged_box_reserve_61bits, 7,     61, 3 ],
+    [tagged_box_reserve_60bits, 15,    60, 4 ],
+    [tagged_box_reserve_59bits, 31,    59, 5 ],
+    [tagged_box_reserve_58bits, 63,    58, 6 ],
+    [tagged_box_reserve_57bits, 127,   57, 7 ],
+    [tagged_box_reserve_56bits, 255,   56, 8 ],
+    [tagged_box_reserve_55bits, 511,   55, 9 ],
+    [tagged_box_reserve_54bits, 1023,  54, 10],
+    [tagged_box_reserve_53bits, 2047,  53, 11],
+    [tagged_box_reserve_52bits, 4095,  52, 12],
+    [tagged_box_reserve_51bits, 8191,  51, 13],
+    [tagged_box_reserve_50bits, 16383, 50, 14],
+    [tagged_box_reserve_49bits, 32767, 49, 15],
+    [tagged_box_reserve_48bits, 65535, 48, 16]
+}
+
+/// The variables used on 32bit targets, which have no free upper bits and store the discriminant in the
+/// lower four bits of pointers aligned to 16 bytes instead. This keeps the same number of discriminants
+/// as the default `60bits` reserved width of 64bit targets
+#[cfg(target_pointer_width = "32")]
+mod variables {
+    use super::Discriminant;
+
+    /// The maximum allowed value of a discriminant, which for 32bit targets is 15
+    pub const MAX_DISCRIMINANT: Discriminant = 15;
+
+    /// The maximum allowed value of a pointer, which for 32bit targets is `2 ^ 32`
+    pub const MAX_POINTER_VALUE: u64 = u32::MAX as u64;
+
+    /// The reserved width of a pointer, which for 32bit targets is 32 bits
+    pub const POINTER_WIDTH: u32 = 32;
+
+    /// A mask to remove the lower free bits of a tagged pointer, which for 32bit targets is `u32::MAX & !0b1111`
+    pub const DISCRIMINANT_MASK: u64 = u32::MAX as u64 & !0b1111;
+
+    /// The total number of upper bits reserved for a discriminant, which for 32bit targets is `0`
+    pub const DISCRIMINANT_BITS: u32 = 0;
+
+    /// The number of lower bits reserved for a discriminant, which for 32bit targets is `4`
+    pub const LOW_DISCRIMINANT_BITS: u32 = 4;
 }
@@ -0,0 +1,179 @@
+use crate::discriminant::Discriminant;
+use core::fmt;
+
+/// An error that occurred while tagging a pointer or allocating its value, returned by the checked
+/// constructors of [`TaggedPointer`] and [`TaggedBox`]
+///
+/// [`TaggedPointer`]: crate::TaggedPointer
+/// [`TaggedBox`]: crate::TaggedBox
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TagError {
+    /// The discriminant was greater than the [`MAX_DISCRIMINANT`] of the tagged pointer
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    DiscriminantOutOfRange {
+        /// The discriminant that was attempted to be stored
+        discriminant: Discriminant,
+        /// The maximum allowed discriminant
+        max: Discriminant,
+    },
+
+    /// The pointer used more bits than the [`POINTER_WIDTH`] of the tagged pointer, so its upper
+    /// bits can't be used to store a discriminant
+    ///
+    /// [`POINTER_WIDTH`]: crate::TaggedPointer::POINTER_WIDTH
+    PointerTooWide {
+        /// The pointer that was attempted to be stored
+        pointer: u64,
+        /// The maximum allowed value of a pointer
+        max: u64,
+    },
+
+    /// The pointer wasn't aligned to the [`MIN_ALIGNMENT`] of the tagged pointer, so its lower
+    /// bits can't be used to store a discriminant
+    ///
+    /// [`MIN_ALIGNMENT`]: crate::TaggedPointer::MIN_ALIGNMENT
+    Misaligned {
+        /// The pointer that was attempted to be stored
+        pointer: u64,
+        /// The alignment the pointer is required to have
+        align: usize,
+    },
+
+    /// Both the pointer and the discriminant were zero, so the tagged pointer would be null
+    Null,
+
+    /// The allocator couldn't allocate memory for the value
+    Alloc(AllocError),
+}
+
+/// The allocator of a [`TaggedBox`] failed to allocate memory, usually because it ran out of memory
+///
+/// [`TaggedBox`]: crate::TaggedBox
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AllocError;
+
+impl TagError {
+    /// Turns the error of a fallible allocation into an [`AllocError`], used by the fallible
+    /// constructors `tagged_box!` generates. Their discriminants are checked at compile time, and a
+    /// pointer that can't be tagged is as unusable as no pointer at all
+    ///
+    /// [`AllocError`]: crate::AllocError
+    #[doc(hidden)]
+    #[inline]
+    pub fn into_alloc_error(self) -> AllocError {
+        debug_assert!(
+            !matches!(self, Self::DiscriminantOutOfRange { .. }),
+            "tagged_box! discriminants are checked at compile time",
+        );
+
+        AllocError
+    }
+}
+
+impl From<AllocError> for TagError {
+    #[inline]
+    fn from(err: AllocError) -> Self {
+        Self::Alloc(err)
+    }
+}
+
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::DiscriminantOutOfRange { discriminant, max } => write!(
+                f,
+                "the discriminant {} is greater than the max discriminant of {}",
+                discriminant, max,
+            ),
+            Self::PointerTooWide { pointer, max } => write!(
+                f,
+                "the pointer {:#x} is greater than the max pointer value of {:#x}",
+                pointer, max,
+            ),
+            Self::Misaligned { pointer, align } => write!(
+                f,
+                "the pointer {:#x} is not aligned to {} bytes",
+                pointer, align,
+            ),
+            Self::Null => f.write_str("the tagged pointer would be null"),
+            Self::Alloc(ref err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TagError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Alloc(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn display() {
+        let discriminant = TagError::DiscriminantOutOfRange {
+            discriminant: 20,
+            max: 15,
+        };
+        assert_eq!(
+            discriminant.to_string(),
+            "the discriminant 20 is greater than the max discriminant of 15",
+        );
+
+        let too_wide = TagError::PointerTooWide {
+            pointer: 0xFFFF_0000_0000_0000,
+            max: 0x0FFF_FFFF_FFFF_FFFF,
+        };
+        assert_eq!(
+            too_wide.to_string(),
+            "the pointer 0xffff000000000000 is greater than the max pointer value of 0xfffffffffffffff",
+        );
+
+        let misaligned = TagError::Misaligned {
+            pointer: 0xF00D_BEEF,
+            align: 8,
+        };
+        assert_eq!(
+            misaligned.to_string(),
+            "the pointer 0xf00dbeef is not aligned to 8 bytes",
+        );
+
+        assert_eq!(TagError::Null.to_string(), "the tagged pointer would be null");
+        assert_eq!(
+            TagError::from(AllocError).to_string(),
+            "memory allocation failed",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_error() {
+        use std::error::Error;
+
+        let error: &dyn Error = &TagError::Misaligned {
+            pointer: 0xF00D_BEEF,
+            align: 8,
+        };
+        assert!(error.source().is_none());
+
+        let error: &dyn Error = &TagError::Alloc(AllocError);
+        assert!(error.source().unwrap().is::<AllocError>());
+    }
+}
@@ -0,0 +1,28 @@
+/// Small values that can be stored directly in the address bits of a [`TaggedBox`] instead of
+/// being allocated, see [`TaggedBox::new_inline`]
+///
+/// Whether a type actually fits depends on the number of free and lower bits the tagged box uses
+/// and is checked at compile time, see [`TaggedBox::INLINE_BITS`]. With the default settings, types
+/// of up to 7 bytes fit on 64bit targets and types of up to 3 bytes on 32bit ones
+///
+/// # Safety
+///
+/// Every byte of the type must always be initialized, so it can't contain any padding
+///
+/// [`TaggedBox`]: crate::TaggedBox
+/// [`TaggedBox::new_inline`]: crate::TaggedBox#new_inline
+/// [`TaggedBox::INLINE_BITS`]: crate::TaggedBox::INLINE_BITS
+pub unsafe trait Immediate: Copy {}
+
+macro_rules! impl_immediate {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            unsafe impl Immediate for $ty {}
+        )*
+    };
+}
+
+impl_immediate!(u8, u16, u32, i8, i16, i32, f32, bool, char);
+
+// Arrays have no padding between their elements
+unsafe impl<T: Immediate, const N: usize> Immediate for [T; N] {}
@@ -106,6 +106,9 @@
 //! Ramping the abstraction up one more notch, we have the [`tagged_box!`] macro, which creates a container-type struct and an
 //! associated `TaggedBox`-backed enum that can be seamlessly transferred between.
 //!
+//! For actually NaN-boxing floats, [`NanBox`] stores either an `f64` or a tagged pointer in 8 bytes, using the payload bits of
+//! quiet NaNs for the pointer.
+//!
 //! ## Crate Settings
 //!
 //! This crate has a few settings that change the number of free and reserved bits, this can be changed by setting `TAGGED_BOX_RESERVED_WIDTH`:
@@ -129,6 +132,28 @@
 //!
 //! However, only one of these may be active at a time, otherwise a `compile_error` will be emitted.
 //!
+//! This setting is only the default, [`TaggedPointer`] and [`TaggedBox`] take the number of free bits as their
+//! `FREE_BITS` const parameter and [`tagged_box!`] accepts `#[tagged_box(free_bits = N)]`, so libraries can
+//! pick the width they need without affecting the rest of the dependency graph.
+//!
+//! Alternatively, the discriminant can be stored in the lower bits of a pointer through the `LOW_BITS` const
+//! parameter, which only relies on alignment and not on the address space of the target. A `TaggedBox` using
+//! `LOW_BITS` over-aligns its allocations so that the lower bits are always free. Both can be used at once,
+//! splitting the discriminant across the upper and lower bits of the pointer for wider discriminants.
+//!
+//! ## 32bit Targets
+//!
+//! 32bit targets use every bit of their pointers for addresses, so there are no free upper bits to speak of.
+//! Instead, they store the discriminant in the lower four bits of every pointer by default, aligning allocations to
+//! 16 bytes. This gives the same 16 discriminants as the default `60bits` setting of 64bit targets, and
+//! `TAGGED_BOX_RESERVED_WIDTH` is ignored.
+//!
+//! ## Features
+//!
+//! - `std`: Implements `std::error::Error` for [`TagError`]
+//! - `derive`: Re-exports `#[derive(Taggable)]` from `tagged-box-derive`, which generates the same
+//!   container as [`tagged_box!`] for an enum declared on its own
+//!
 //! [`Box`]: (https://doc.rust-lang.org/std/boxed/struct.Box.html)
 //! [settings]: #crate-settings
 //! [NaN-Boxing]: https://wingolog.org/archives/2011/05/18/value-representation-in-javascript-implementations
@@ -137,12 +162,18 @@
 //! [enum discriminant]: https://doc.rust-lang.org/reference/items/enumerations.html
 //! [`TaggedBox`]: crate::TaggedBox
 //! [`TaggedPointer`]: crate::TaggedPointer
+//! [`NanBox`]: crate::NanBox
+//! [`TagError`]: crate::TagError
 //! [`tagged_box!`]: macro.tagged_box.html
 
 // `no_std` is supported, but the `alloc` crate is required, as
 // tagged values are allocated to the heap.
 extern crate alloc;
 
+// `std` is only used to implement `std::error::Error`
+#[cfg(feature = "std")]
+extern crate std;
+
 // TODO: Much of this crate can be `const fn` once certain features from #57563 come through
 // Needed features from #57563:
 //    - Dereferencing raw pointers via #51911
@@ -152,22 +183,6 @@ extern crate alloc;
 //    - Panics via #51999
 //
 // https://github.com/rust-lang/rust/issues/57563
-//
-// TODO: Figure out how to allow `?Sized` within `TaggedBox` and `TaggedPointer` to allow for
-// parity with `Box`
-
-// 32bit arches use all 32 bits of their pointers, so an extra 16bit tag will have to be added.
-// Any benefit from tagging is kinda lost, but it's worth it to allow compatibility for this crate
-// between 32bit and 64bit arches.
-//
-//
-// Possible `TaggedPointer` structure on 32bit arches:
-// ```rust
-// pub struct TaggedPointer {
-//     ptr: usize,
-//     tag: u8,
-// }
-// ```
 
 // Will support 128bits if they ever become mainstream, but we'll cross that bridge when we get to it.
 #[cfg(not(any(target_pointer_width = "64", target_pointer_width = "32")))]
@@ -185,45 +200,55 @@ macro_rules! impl_fmt {
         )+
     };
 
-    // Special case because I don't like rewriting stuff
-    (impl[T: TaggableInner] $ty:ty => $($fmt:ident),+) => {
-        $(
-            impl<T: TaggableInner> fmt::$fmt for $ty {
-                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                    fmt::$fmt::fmt(&self.as_u64(), f)
-                }
-            }
-        )+
-    };
-
-    // General-purpose arm, used as follows:
+    // General-purpose arm for generic types, used as follows:
     // ```rust
-    // impl_fmt! {
-    //     impl[T: Debug] TaggedBox<T> => LowerHex,
-    //     impl[T] TaggedBox<T> => UpperHex
-    // }
+    // impl_fmt!(impl[T: TaggableInner] TaggedBox<T> => LowerHex, UpperHex);
     // ```
-    ($(impl[$($generic:tt)*] $ty:ty => $fmt:ident),+) => {
-        $(
-            impl<$($generic)*> fmt::$fmt for $ty {
-                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                    fmt::$fmt::fmt(&self.as_usize(), f)
-                }
+    (impl[$($generic:tt)*] $ty:ty => $fmt:ident $(, $rest:ident)*) => {
+        impl<$($generic)*> fmt::$fmt for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::$fmt::fmt(&self.as_u64(), f)
             }
-        )+
+        }
+
+        impl_fmt!(impl[$($generic)*] $ty => $($rest),*);
     };
+    (impl[$($generic:tt)*] $ty:ty =>) => {};
 }
 
+mod allocator;
+mod atomic_tagged_pointer;
 pub mod discriminant;
+mod error;
+mod immediate;
 pub mod manually_impl_enum;
+mod nan_box;
+mod provenance;
+pub mod rc;
+mod shared;
+pub mod sync;
 mod taggable;
 mod tagged_box;
 #[macro_use]
 mod tagged_box_macro;
+mod tagged_inline;
 mod tagged_pointer;
+mod variant_type;
 
 pub use crate::tagged_box::TaggedBox;
+pub use allocator::{Global, RawAllocator};
+pub use atomic_tagged_pointer::AtomicTaggedPointer;
 #[doc(inline)]
 pub use discriminant::Discriminant;
+pub use error::{AllocError, TagError};
+pub use immediate::Immediate;
+pub use nan_box::NanBox;
+pub use rc::TaggedRc;
+pub use sync::TaggedArc;
 pub use taggable::{TaggableContainer, TaggableInner};
+pub use tagged_inline::TaggedInline;
 pub use tagged_pointer::TaggedPointer;
+pub use variant_type::VariantType;
+
+#[cfg(feature = "derive")]
+pub use tagged_box_derive::Taggable;
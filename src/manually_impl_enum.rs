@@ -16,7 +16,7 @@
 //! This code will be generated:
 //!
 //! ```rust
-//! # use tagged_box::{TaggedBox, TaggableContainer, TaggableInner};
+//! # use tagged_box::{Discriminant, TaggedBox, TaggableContainer, TaggableInner, VariantType};
 //! # use core::mem::ManuallyDrop;
 //! #
 //! #[repr(transparent)]
@@ -58,8 +58,8 @@
 //!         }
 //!
 //!         match self {
-//!             Self::Integer(value) => TaggedBox::new(value, EnumCounter::Integer as _),
-//!             Self::Boolean(value) => TaggedBox::new(value, EnumCounter::Boolean as _),
+//!             Self::Integer(value) => unsafe { TaggedBox::new(value, EnumCounter::Integer as _) },
+//!             Self::Boolean(value) => unsafe { TaggedBox::new(value, EnumCounter::Boolean as _) },
 //!         }
 //!     }
 //!
@@ -103,6 +103,34 @@
 //!             }
 //!         }
 //!     }
+//!
+//!     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+//!         enum EnumCounter {
+//!             Integer,
+//!             Boolean,
+//!         }
+//!
+//!         match tagged.discriminant() {
+//!             discrim if discrim == EnumCounter::Integer as _ =>
+//!                 TaggedBox::drop_inner::<i32>(tagged),
+//!             discrim if discrim == EnumCounter::Boolean as _ =>
+//!                 TaggedBox::drop_inner::<bool>(tagged),
+//!             _ => panic!(),
+//!         }
+//!     }
+//!
+//!     fn variant_type(discriminant: Discriminant) -> Option<VariantType> {
+//!         enum EnumCounter {
+//!             Integer,
+//!             Boolean,
+//!         }
+//!
+//!         match discriminant {
+//!             discrim if discrim == EnumCounter::Integer as _ => Some(VariantType::of::<i32>()),
+//!             discrim if discrim == EnumCounter::Boolean as _ => Some(VariantType::of::<bool>()),
+//!             _ => None,
+//!         }
+//!     }
 //! }
 //! ```
 //!
@@ -110,8 +138,13 @@
 //! The first piece of code generated is this struct:
 //!
 //! ```rust
-//! # use tagged_box::{TaggedBox, TaggableContainer, TaggableInner};
-//! # enum Item {}
+//! # use tagged_box::{tagged_box, TaggedBox};
+//! # tagged_box! {
+//! #     struct ItemContainer, enum Item {
+//! #         Integer(i32),
+//! #         Boolean(bool),
+//! #     }
+//! # }
 //! #
 //! #[repr(transparent)]
 //! struct Container {
@@ -129,9 +162,14 @@
 //!
 //! Then we have the `Item` enum, which is a representation of what the `TaggedBox` is holding, and can be conveniently
 //! gotten from any `Container` instance. This has the [`TaggableInner`] trait implemented on it, which allows us to
-//! have convenient things like `Clone`, `PartialEq` and `Ord`.
+//! have convenient things like `Clone`, `PartialEq` and `Ord`, and lets [`TaggedBox`] run the destructor of
+//! whichever variant it holds and free its allocation when it's dropped. Lastly, `variant_type` is the type table
+//! that lets [`TaggedBox::downcast_ref`] and friends check the stored type without `unsafe`, it's optional and
+//! downcasts always fail without it.
 //!
 //! [`tagged_box!`]: ../macro.tagged_box.html
 //! [`Box`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html
 //! [`TaggableContainer`]: crate::TaggableContainer
 //! [`TaggableInner`]: crate::TaggableInner
+//! [`TaggedBox`]: crate::TaggedBox
+//! [`TaggedBox::downcast_ref`]: crate::TaggedBox#downcast_ref
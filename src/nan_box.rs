@@ -0,0 +1,406 @@
+use crate::{discriminant::Discriminant, error::TagError, provenance};
+use core::fmt;
+
+/// A NaN-boxed value, holding either an `f64` or a tagged pointer in 8 bytes without allocating
+///
+/// Every `f64` whose upper 13 bits are all set is a (negative, quiet) NaN, so those bit patterns are
+/// free to be used for other values. A `NanBox` uses them to store a pointer of up to
+/// [`POINTER_WIDTH`] bits along with a tag of up to [`MAX_TAG`], which can be used to tell the
+/// kinds of pointers apart. Every other bit pattern is the `f64` itself
+///
+/// To keep the two from overlapping, every NaN stored in a `NanBox` is replaced with the
+/// [`CANONICAL_NAN`], so NaN payloads aren't preserved. This also means that the `PartialEq`,
+/// `Eq` and `Hash` impls compare the bits of a `NanBox`, where all NaNs are equal to each other
+///
+/// ```rust
+/// use tagged_box::NanBox;
+///
+/// let float = NanBox::from_f64(1.5);
+/// assert!(!float.is_pointer());
+/// assert_eq!(float.as_f64(), Some(1.5));
+///
+/// let string = "Hello from a NaN";
+/// let pointer = NanBox::from_ptr(string.as_ptr(), 2);
+/// assert!(pointer.is_pointer());
+/// assert_eq!(pointer.as_f64(), None);
+/// assert_eq!(pointer.tag(), Some(2));
+/// assert_eq!(pointer.as_ptr::<u8>(), Some(string.as_ptr() as *mut u8));
+///
+/// assert_eq!(NanBox::from_f64(f64::NAN), NanBox::from_f64(-f64::NAN));
+/// ```
+///
+/// Like a [`TaggedPointer`], the bits are kept in a pointer and pointers are tagged through strict
+/// provenance operations, so the pointer that comes back out of [`NanBox::as_ptr`] keeps the
+/// provenance of the one that was boxed. Floats are stored as pointers without any provenance
+///
+/// [`TaggedPointer`]: crate::TaggedPointer
+/// [`NanBox::as_ptr`]: crate::NanBox#as_ptr
+/// [`POINTER_WIDTH`]: crate::NanBox::POINTER_WIDTH
+/// [`MAX_TAG`]: crate::NanBox::MAX_TAG
+/// [`CANONICAL_NAN`]: crate::NanBox::CANONICAL_NAN
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NanBox {
+    /// The lower bits of the value, which hold the whole pointer along with its provenance
+    low: *mut (),
+    /// The upper bits of the value, which don't fit into a pointer on 32bit targets
+    #[cfg(not(target_pointer_width = "64"))]
+    high: u32,
+}
+
+// Safety: The pointer is never dereferenced by the `NanBox`, which holds it like an integer would
+unsafe impl Send for NanBox {}
+unsafe impl Sync for NanBox {}
+
+impl NanBox {
+    /// The bits of the NaN that every NaN is replaced with, which is the same as [`f64::NAN`]
+    ///
+    /// [`f64::NAN`]: https://doc.rust-lang.org/core/primitive.f64.html#associatedconstant.NAN
+    pub const CANONICAL_NAN: u64 = 0x7FF8_0000_0000_0000;
+
+    /// The bits that are set for every pointer, the sign bit plus those of a quiet NaN
+    const POINTER_MASK: u64 = 0xFFF8_0000_0000_0000;
+
+    /// The number of bits used by pointers
+    pub const POINTER_WIDTH: u32 = 48;
+
+    /// The max value of a pointer that can be stored
+    pub const MAX_POINTER_VALUE: u64 = (1 << Self::POINTER_WIDTH) - 1;
+
+    /// The number of bits used by the tags of pointers
+    pub const TAG_BITS: u32 = 3;
+
+    /// The max tag that can be stored alongside a pointer
+    pub const MAX_TAG: Discriminant = (1 << Self::TAG_BITS) - 1;
+
+    /// Creates a new `NanBox` holding a float, replacing NaNs with the [`CANONICAL_NAN`]
+    ///
+    /// [`CANONICAL_NAN`]: crate::NanBox::CANONICAL_NAN
+    #[inline]
+    pub fn from_f64(float: f64) -> Self {
+        let bits = if float.is_nan() {
+            Self::CANONICAL_NAN
+        } else {
+            float.to_bits()
+        };
+
+        Self::from_bits(bits)
+    }
+
+    /// Creates a new `NanBox` holding a pointer and its tag
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `tag` is greater than [`MAX_TAG`] or if `ptr` is greater than
+    /// [`MAX_POINTER_VALUE`], use [`NanBox::try_from_ptr`] for values that can't be trusted
+    ///
+    /// [`MAX_TAG`]: crate::NanBox::MAX_TAG
+    /// [`MAX_POINTER_VALUE`]: crate::NanBox::MAX_POINTER_VALUE
+    /// [`NanBox::try_from_ptr`]: crate::NanBox#try_from_ptr
+    #[inline]
+    pub fn from_ptr<T>(ptr: *const T, tag: Discriminant) -> Self {
+        let addr = provenance::addr(ptr as *mut ()) as u64;
+        debug_assert!(
+            tag <= Self::MAX_TAG,
+            "Attempted to store a tag of {} while the max value is {}",
+            tag,
+            Self::MAX_TAG,
+        );
+        debug_assert!(
+            addr <= Self::MAX_POINTER_VALUE,
+            "Attempted to store a pointer wider than {} bits",
+            Self::POINTER_WIDTH,
+        );
+
+        Self::pack(ptr as *mut (), tag)
+    }
+
+    /// Creates a new `NanBox` holding a pointer and its tag, returning an error if either
+    /// can't be stored
+    ///
+    /// ```rust
+    /// use tagged_box::{NanBox, TagError};
+    ///
+    /// let value = 10;
+    /// assert!(NanBox::try_from_ptr(&value, 7).is_ok());
+    /// assert_eq!(
+    ///     NanBox::try_from_ptr(&value, 8),
+    ///     Err(TagError::DiscriminantOutOfRange { discriminant: 8, max: 7 }),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TagError::DiscriminantOutOfRange`] if `tag` is greater than [`MAX_TAG`] and
+    /// [`TagError::PointerTooWide`] if `ptr` is greater than [`MAX_POINTER_VALUE`]
+    ///
+    /// [`MAX_TAG`]: crate::NanBox::MAX_TAG
+    /// [`MAX_POINTER_VALUE`]: crate::NanBox::MAX_POINTER_VALUE
+    /// [`TagError::DiscriminantOutOfRange`]: crate::TagError::DiscriminantOutOfRange
+    /// [`TagError::PointerTooWide`]: crate::TagError::PointerTooWide
+    #[inline]
+    pub fn try_from_ptr<T>(ptr: *const T, tag: Discriminant) -> Result<Self, TagError> {
+        if tag > Self::MAX_TAG {
+            return Err(TagError::DiscriminantOutOfRange {
+                discriminant: tag,
+                max: Self::MAX_TAG,
+            });
+        }
+
+        let addr = provenance::addr(ptr as *mut ()) as u64;
+        if addr > Self::MAX_POINTER_VALUE {
+            return Err(TagError::PointerTooWide {
+                pointer: addr,
+                max: Self::MAX_POINTER_VALUE,
+            });
+        }
+
+        Ok(Self::pack(ptr as *mut (), tag))
+    }
+
+    /// Stores the NaN and tag bits into the upper bits of `ptr`, keeping its provenance
+    #[inline]
+    fn pack(ptr: *mut (), tag: Discriminant) -> Self {
+        let upper =
+            Self::POINTER_MASK | ((tag as u64 & Self::MAX_TAG as u64) << Self::POINTER_WIDTH);
+
+        Self::with_bits(ptr, |addr| upper | (addr as u64 & Self::MAX_POINTER_VALUE))
+    }
+
+    /// Creates a `NanBox` holding the bits returned by `map`, which is given the address of `ptr`.
+    /// The lower bits keep the provenance of `ptr`
+    #[inline]
+    #[cfg(target_pointer_width = "64")]
+    fn with_bits(ptr: *mut (), map: impl FnOnce(usize) -> u64) -> Self {
+        Self {
+            low: provenance::map_addr(ptr, |addr| map(addr) as usize),
+        }
+    }
+
+    /// Creates a `NanBox` holding the bits returned by `map`, which is given the address of `ptr`.
+    /// The lower bits keep the provenance of `ptr`
+    #[inline]
+    #[cfg(not(target_pointer_width = "64"))]
+    fn with_bits(ptr: *mut (), map: impl FnOnce(usize) -> u64) -> Self {
+        let bits = map(provenance::addr(ptr));
+
+        Self {
+            low: provenance::with_addr(ptr, bits as u32 as usize),
+            high: (bits >> 32) as u32,
+        }
+    }
+
+    /// Creates a `NanBox` from its raw bits, the inverse of [`NanBox::to_bits`]. Pointers created
+    /// this way have no provenance, so they can't be dereferenced
+    ///
+    /// [`NanBox::to_bits`]: crate::NanBox#to_bits
+    #[inline]
+    #[cfg(target_pointer_width = "64")]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self {
+            low: provenance::without_provenance(bits as usize),
+        }
+    }
+
+    /// Creates a `NanBox` from its raw bits, the inverse of [`NanBox::to_bits`]. Pointers created
+    /// this way have no provenance, so they can't be dereferenced
+    ///
+    /// [`NanBox::to_bits`]: crate::NanBox#to_bits
+    #[inline]
+    #[cfg(not(target_pointer_width = "64"))]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self {
+            low: provenance::without_provenance(bits as u32 as usize),
+            high: (bits >> 32) as u32,
+        }
+    }
+
+    /// Returns the raw bits of the `NanBox`
+    #[inline]
+    #[cfg(target_pointer_width = "64")]
+    pub fn to_bits(self) -> u64 {
+        provenance::addr(self.low) as u64
+    }
+
+    /// Returns the raw bits of the `NanBox`
+    #[inline]
+    #[cfg(not(target_pointer_width = "64"))]
+    pub fn to_bits(self) -> u64 {
+        ((self.high as u64) << 32) | provenance::addr(self.low) as u64
+    }
+
+    /// Returns true if the `NanBox` holds a pointer
+    #[inline]
+    pub fn is_pointer(self) -> bool {
+        self.to_bits() & Self::POINTER_MASK == Self::POINTER_MASK
+    }
+
+    /// Returns true if the `NanBox` holds a float
+    #[inline]
+    pub fn is_f64(self) -> bool {
+        !self.is_pointer()
+    }
+
+    /// Returns the float held by the `NanBox`, or `None` if it holds a pointer
+    #[inline]
+    pub fn as_f64(self) -> Option<f64> {
+        if self.is_pointer() {
+            None
+        } else {
+            Some(f64::from_bits(self.to_bits()))
+        }
+    }
+
+    /// Returns the pointer held by the `NanBox` with the provenance of the pointer it was created
+    /// from, or `None` if it holds a float
+    #[inline]
+    pub fn as_ptr<T>(self) -> Option<*mut T> {
+        if self.is_pointer() {
+            let ptr = provenance::map_addr(self.low, |addr| {
+                (addr as u64 & Self::MAX_POINTER_VALUE) as usize
+            });
+
+            Some(ptr as *mut T)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the tag of the pointer held by the `NanBox`, or `None` if it holds a float
+    #[inline]
+    pub fn tag(self) -> Option<Discriminant> {
+        if self.is_pointer() {
+            Some(((self.to_bits() >> Self::POINTER_WIDTH) & Self::MAX_TAG as u64) as Discriminant)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<f64> for NanBox {
+    #[inline]
+    fn from(float: f64) -> Self {
+        Self::from_f64(float)
+    }
+}
+
+impl fmt::Debug for NanBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_f64() {
+            Some(float) => f.debug_tuple("NanBox").field(&float).finish(),
+            None => f
+                .debug_struct("NanBox")
+                .field("ptr", &(self.to_bits() & Self::MAX_POINTER_VALUE))
+                .field("tag", &self.tag().unwrap_or_default())
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{boxed::Box, format};
+
+    #[test]
+    fn floats() {
+        let floats = [
+            0.0,
+            -0.0,
+            1.5,
+            -1.5,
+            f64::MAX,
+            f64::MIN,
+            f64::MIN_POSITIVE,
+            f64::EPSILON,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+
+        for &float in floats.iter() {
+            let boxed = NanBox::from_f64(float);
+            assert!(boxed.is_f64());
+            assert!(!boxed.is_pointer());
+            assert_eq!(boxed.as_f64().unwrap().to_bits(), float.to_bits());
+            assert_eq!(boxed.as_ptr::<u8>(), None);
+            assert_eq!(boxed.tag(), None);
+        }
+    }
+
+    #[test]
+    fn canonical_nan() {
+        let nans = [
+            f64::NAN,
+            -f64::NAN,
+            f64::from_bits(0x7FF0_0000_0000_0001),
+            f64::from_bits(0xFFF8_0000_0000_0000),
+            f64::from_bits(0xFFFF_FFFF_FFFF_FFFF),
+        ];
+
+        for &nan in nans.iter() {
+            let boxed = NanBox::from_f64(nan);
+            assert!(!boxed.is_pointer());
+            assert_eq!(boxed.to_bits(), NanBox::CANONICAL_NAN);
+            assert!(boxed.as_f64().unwrap().is_nan());
+        }
+        assert_eq!(f64::NAN.to_bits(), NanBox::CANONICAL_NAN);
+    }
+
+    #[test]
+    fn pointers() {
+        let value = Box::into_raw(Box::new(100usize));
+
+        for tag in 0..=NanBox::MAX_TAG {
+            let boxed = NanBox::from_ptr(value, tag);
+            assert!(boxed.is_pointer());
+            assert_eq!(boxed.as_f64(), None);
+            assert_eq!(boxed.tag(), Some(tag));
+            assert_eq!(boxed.as_ptr::<usize>(), Some(value));
+            assert!(f64::from_bits(boxed.to_bits()).is_nan());
+        }
+
+        let boxed = NanBox::from_ptr(value, 5);
+        unsafe {
+            *boxed.as_ptr::<usize>().unwrap() += 1;
+            assert_eq!(*value, 101);
+
+            drop(Box::from_raw(boxed.as_ptr::<usize>().unwrap()));
+        }
+
+        // The pointer is kept in the lower bits on every target, while the value stays 8 bytes
+        assert_eq!(core::mem::size_of::<NanBox>(), 8);
+
+        // Null pointers are still pointers, not floats
+        let null = NanBox::from_ptr(core::ptr::null::<u8>(), 0);
+        assert!(null.is_pointer());
+        assert_eq!(null.as_ptr::<u8>(), Some(core::ptr::null_mut()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            NanBox::try_from_ptr(core::ptr::null::<u8>(), 8),
+            Err(TagError::DiscriminantOutOfRange {
+                discriminant: 8,
+                max: 7,
+            }),
+        );
+
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            NanBox::try_from_ptr(provenance::without_provenance(1 << 48) as *const u8, 0),
+            Err(TagError::PointerTooWide {
+                pointer: 1 << 48,
+                max: NanBox::MAX_POINTER_VALUE,
+            }),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", NanBox::from_f64(1.5)), "NanBox(1.5)");
+        assert_eq!(
+            format!("{:?}", NanBox::from_ptr(core::ptr::null::<u8>(), 3)),
+            "NanBox { ptr: 0, tag: 3 }",
+        );
+    }
+}
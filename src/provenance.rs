@@ -0,0 +1,103 @@
+//! Address manipulation that keeps the provenance of pointers intact, using the strict provenance
+//! methods of pointers when they're available and a polyfill based on `wrapping_offset` when they aren't.
+//! `build.rs` enables `tagged_box_strict_provenance` on compilers that have them
+
+/// Gets the address of a pointer without exposing its provenance
+#[inline]
+#[cfg(tagged_box_strict_provenance)]
+pub(crate) fn addr(ptr: *mut ()) -> usize {
+    ptr.addr()
+}
+
+/// Gets the address of a pointer without exposing its provenance
+#[inline]
+#[cfg(not(tagged_box_strict_provenance))]
+pub(crate) fn addr(ptr: *mut ()) -> usize {
+    ptr as usize
+}
+
+/// Creates a new pointer with the given address and the provenance of `ptr`
+#[inline]
+#[cfg(tagged_box_strict_provenance)]
+pub(crate) fn with_addr(ptr: *mut (), addr: usize) -> *mut () {
+    ptr.with_addr(addr)
+}
+
+/// Creates a new pointer with the given address and the provenance of `ptr`
+#[inline]
+#[cfg(not(tagged_box_strict_provenance))]
+pub(crate) fn with_addr(ptr: *mut (), addr: usize) -> *mut () {
+    // Offsetting the original pointer keeps its provenance, unlike casting `addr` into a pointer
+    let offset = addr.wrapping_sub(self::addr(ptr)) as isize;
+
+    (ptr as *mut u8).wrapping_offset(offset) as *mut ()
+}
+
+/// Creates a new pointer by mapping the address of `ptr`, keeping its provenance
+#[inline]
+pub(crate) fn map_addr(ptr: *mut (), map: impl FnOnce(usize) -> usize) -> *mut () {
+    with_addr(ptr, map(addr(ptr)))
+}
+
+/// Creates a pointer from an address that was previously exposed, used for pointers that
+/// were given to us as integers
+#[inline]
+#[cfg(tagged_box_strict_provenance)]
+pub(crate) fn with_exposed_provenance(addr: usize) -> *mut () {
+    core::ptr::with_exposed_provenance_mut(addr)
+}
+
+/// Creates a pointer from an address that was previously exposed, used for pointers that
+/// were given to us as integers
+#[inline]
+#[cfg(not(tagged_box_strict_provenance))]
+pub(crate) fn with_exposed_provenance(addr: usize) -> *mut () {
+    addr as *mut ()
+}
+
+/// Creates a pointer without any provenance, which can never be dereferenced
+#[inline]
+#[cfg(tagged_box_strict_provenance)]
+pub(crate) const fn without_provenance(addr: usize) -> *mut () {
+    core::ptr::without_provenance_mut(addr)
+}
+
+/// Creates a pointer without any provenance, which can never be dereferenced
+#[inline]
+#[cfg(not(tagged_box_strict_provenance))]
+pub(crate) const fn without_provenance(addr: usize) -> *mut () {
+    addr as *mut ()
+}
+
+/// Replaces the data pointer of a possibly fat pointer with `data`, keeping the metadata of `ptr`
+/// and the provenance of `data`
+#[inline]
+pub(crate) fn with_data_ptr<T: ?Sized>(mut ptr: *mut T, data: *mut u8) -> *mut T {
+    // The data pointer is the first word of every pointer, this is how the unstable
+    // `<*mut T>::set_ptr_value` is implemented
+    unsafe { *(&mut ptr as *mut *mut T as *mut *mut u8) = data };
+
+    ptr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut integer = 100usize;
+        let ptr = &mut integer as *mut usize as *mut ();
+
+        let tagged = map_addr(ptr, |addr| addr | 0b111);
+        assert_eq!(addr(tagged), addr(ptr) | 0b111);
+
+        let untagged = map_addr(tagged, |addr| addr & !0b111);
+        assert_eq!(untagged, ptr);
+
+        unsafe {
+            *(untagged as *mut usize) = 200;
+        }
+        assert_eq!(integer, 200);
+    }
+}
@@ -0,0 +1,156 @@
+//! Single threaded reference counted tagged pointers
+//!
+//! A [`TaggedRc`] is the shared counterpart of a [`TaggedBox`], storing the discriminant in the
+//! pointer and its strong and weak counts in a header right before the value, so every handle is
+//! still a single pointer wide and cloning one only increments a count.
+//! For sharing values across threads, see [`TaggedArc`]
+//!
+//! ```rust
+//! use tagged_box::{rc::TaggedRc, tagged_box};
+//!
+//! tagged_box! {
+//!     #[derive(Debug, Clone, PartialEq)]
+//!     struct Container, enum Item {
+//!         Integer(i32),
+//!         Boolean(bool),
+//!     }
+//! }
+//!
+//! // Safety: `Item` stores an `i32` for its first variant
+//! let shared: TaggedRc<Item> = unsafe { TaggedRc::new(10i32, 0) };
+//! let other = shared.clone();
+//!
+//! assert!(TaggedRc::ptr_eq(&shared, &other));
+//! assert_eq!(TaggedRc::strong_count(&shared), 2);
+//! assert_eq!(other.to_inner(), Item::Integer(10));
+//! ```
+//!
+//! [`TaggedBox`]: crate::TaggedBox
+//! [`TaggedArc`]: crate::sync::TaggedArc
+
+use crate::{
+    allocator::Global,
+    discriminant::{DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    shared::{self, Shared},
+};
+use core::cell::Cell;
+
+/// A single threaded reference counted pointer to a tagged value, see the [module level docs] for more
+///
+/// Like [`TaggedBox`], `T` is the enum the value is a variant of and the type of the value itself
+/// is given by the caller, so the methods that access it are unsafe. The value is dropped once the
+/// last `TaggedRc` is dropped, and its allocation once the last [`Weak`] is
+///
+/// [module level docs]: crate::rc
+/// [`TaggedBox`]: crate::TaggedBox
+/// [`Weak`]: crate::rc::Weak
+pub type TaggedRc<
+    T,
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A = Global,
+> = Shared<T, Cell<usize>, FREE_BITS, LOW_BITS, A>;
+
+/// A non-owning pointer to a [`TaggedRc`]'s value, which doesn't keep the value alive but keeps
+/// its allocation from being freed
+///
+/// [`TaggedRc`]: crate::rc::TaggedRc
+pub type Weak<
+    T,
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A = Global,
+> = shared::Weak<T, Cell<usize>, FREE_BITS, LOW_BITS, A>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TagError;
+    use crate::tagged_box;
+    use alloc::{rc::Rc, string::String};
+
+    tagged_box! {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            Text(String),
+            Counted(Rc<()>),
+            Nothing,
+        }
+    }
+
+    #[test]
+    fn counts() {
+        let counter = Rc::new(());
+
+        let shared: TaggedRc<Item> = unsafe { TaggedRc::new(counter.clone(), 1) };
+        assert_eq!(TaggedRc::strong_count(&shared), 1);
+        assert_eq!(TaggedRc::weak_count(&shared), 0);
+
+        let other = shared.clone();
+        let weak = TaggedRc::downgrade(&shared);
+        assert_eq!(TaggedRc::strong_count(&shared), 2);
+        assert_eq!(TaggedRc::weak_count(&shared), 1);
+        assert_eq!(weak.discriminant(), 1);
+        assert!(TaggedRc::ptr_eq(&shared, &other));
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        drop(shared);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        assert_eq!(
+            weak.upgrade().unwrap().to_inner(),
+            Item::Counted(counter.clone())
+        );
+
+        drop(other);
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert_eq!(weak.strong_count(), 0);
+        assert_eq!(weak.weak_count(), 1);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn mutation() {
+        let mut shared: TaggedRc<Item> = unsafe { TaggedRc::new(String::from("Hello"), 0) };
+
+        unsafe {
+            TaggedRc::get_mut::<String>(&mut shared)
+                .unwrap()
+                .push_str(", world");
+
+            let weak = TaggedRc::downgrade(&shared);
+            assert!(TaggedRc::get_mut::<String>(&mut shared).is_none());
+            drop(weak);
+
+            let other = shared.clone();
+            TaggedRc::make_mut::<String>(&mut shared).push('!');
+
+            assert_eq!(shared.as_ref::<String>(), "Hello, world!");
+            assert_eq!(other.as_ref::<String>(), "Hello, world");
+            assert_eq!(TaggedRc::strong_count(&other), 1);
+
+            // Unique values are mutated in place
+            let ptr = shared.as_ptr::<String>();
+            TaggedRc::make_mut::<String>(&mut shared).push('!');
+            assert_eq!(shared.as_ptr::<String>(), ptr);
+        }
+    }
+
+    #[test]
+    fn traits() {
+        let shared: TaggedRc<Item> = unsafe { TaggedRc::new(String::from("Hello"), 0) };
+        let nothing: TaggedRc<Item> = unsafe { TaggedRc::new((), 2) };
+
+        assert_eq!(shared, shared.clone());
+        assert_ne!(shared, nothing);
+        assert_eq!(nothing.to_inner(), Item::Nothing);
+        assert_eq!(alloc::format!("{:?}", shared), "Text(\"Hello\")");
+        assert_eq!(
+            unsafe { TaggedRc::<Item>::try_new(0, <crate::TaggedPointer>::MAX_DISCRIMINANT + 1) }
+                .map(|_| ()),
+            Err(TagError::DiscriminantOutOfRange {
+                discriminant: <crate::TaggedPointer>::MAX_DISCRIMINANT + 1,
+                max: <crate::TaggedPointer>::MAX_DISCRIMINANT,
+            }),
+        );
+    }
+}
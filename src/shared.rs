@@ -0,0 +1,935 @@
+//! The reference counted pointers behind [`TaggedRc`] and [`TaggedArc`], which only differ in the
+//! [`RefCount`] used for their counts
+//!
+//! Every allocation starts with a [`Header`] holding the reference counts and everything needed
+//! to drop and deallocate the value without knowing its type, directly followed by the value itself.
+//! The tagged pointer points to the value, so the header always sits right before it
+//!
+//! [`TaggedRc`]: crate::rc::TaggedRc
+//! [`TaggedArc`]: crate::sync::TaggedArc
+
+use crate::{
+    allocator::RawAllocator,
+    discriminant::Discriminant,
+    error::{AllocError, TagError},
+    taggable::TaggableInner,
+    tagged_box::TaggedBox,
+    tagged_pointer::TaggedPointer,
+};
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    cmp, fmt,
+    hash::{Hash, Hasher},
+    hint,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ptr,
+    sync::atomic::{self, AtomicUsize, Ordering},
+};
+
+/// A reference count, either a plain [`Cell`] for single threaded use or an [`AtomicUsize`].
+/// It bounds the public [`Shared`] pointers, but can't be named outside of the crate
+pub trait RefCount {
+    fn new(count: usize) -> Self;
+
+    fn load(&self) -> usize;
+
+    /// Increments the count, panicking if it overflows
+    fn increment(&self);
+
+    /// Increments the count unless it's zero, returning whether it was incremented
+    fn increment_nonzero(&self) -> bool;
+
+    /// Decrements the count, returning true if it reached zero
+    fn decrement(&self) -> bool;
+
+    /// Increments a weak count, which may be locked by [`RefCount::is_unique`]
+    fn increment_weak(&self) {
+        self.increment();
+    }
+
+    /// Returns true if `strong` is the only strong reference and there are no weak references
+    fn is_unique(strong: &Self, weak: &Self) -> bool;
+}
+
+impl RefCount for Cell<usize> {
+    #[inline]
+    fn new(count: usize) -> Self {
+        Cell::new(count)
+    }
+
+    #[inline]
+    fn load(&self) -> usize {
+        self.get()
+    }
+
+    #[inline]
+    fn increment(&self) {
+        self.set(
+            self.get()
+                .checked_add(1)
+                .expect("reference count overflowed"),
+        );
+    }
+
+    #[inline]
+    fn increment_nonzero(&self) -> bool {
+        if self.get() == 0 {
+            false
+        } else {
+            self.increment();
+            true
+        }
+    }
+
+    #[inline]
+    fn decrement(&self) -> bool {
+        self.set(self.get() - 1);
+        self.get() == 0
+    }
+
+    #[inline]
+    fn is_unique(strong: &Self, weak: &Self) -> bool {
+        strong.get() == 1 && weak.get() == 1
+    }
+}
+
+/// The weak count of an atomic allocation while [`RefCount::is_unique`] is checking the strong count
+const LOCKED: usize = usize::MAX;
+
+// The orderings follow the ones used by `alloc::sync::Arc`
+impl RefCount for AtomicUsize {
+    #[inline]
+    fn new(count: usize) -> Self {
+        AtomicUsize::new(count)
+    }
+
+    #[inline]
+    fn load(&self) -> usize {
+        self.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn increment(&self) {
+        let old = self.fetch_add(1, Ordering::Relaxed);
+        if old > isize::MAX as usize {
+            panic!("reference count overflowed");
+        }
+    }
+
+    #[inline]
+    fn increment_nonzero(&self) -> bool {
+        let mut count = self.load(Ordering::Relaxed);
+        loop {
+            if count == 0 {
+                return false;
+            }
+            if count > isize::MAX as usize {
+                panic!("reference count overflowed");
+            }
+
+            match self.compare_exchange_weak(count, count + 1, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => return true,
+                Err(current) => count = current,
+            }
+        }
+    }
+
+    #[inline]
+    fn decrement(&self) -> bool {
+        if self.fetch_sub(1, Ordering::Release) != 1 {
+            return false;
+        }
+
+        // Synchronize with every other decrement before the value or allocation is destroyed
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+
+    #[inline]
+    fn increment_weak(&self) {
+        let mut count = self.load(Ordering::Relaxed);
+        loop {
+            if count == LOCKED {
+                hint::spin_loop();
+                count = self.load(Ordering::Relaxed);
+                continue;
+            }
+            if count > isize::MAX as usize {
+                panic!("reference count overflowed");
+            }
+
+            match self.compare_exchange_weak(count, count + 1, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => count = current,
+            }
+        }
+    }
+
+    #[inline]
+    fn is_unique(strong: &Self, weak: &Self) -> bool {
+        // Lock the weak count so that no strong reference can be downgraded while the strong
+        // count is checked, otherwise a new weak reference could be upgraded later on
+        if weak
+            .compare_exchange(1, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            let unique = strong.load(Ordering::Acquire) == 1;
+            weak.store(1, Ordering::Release);
+
+            unique
+        } else {
+            false
+        }
+    }
+}
+
+/// The header stored right before every shared value
+#[repr(C)]
+struct Header<C> {
+    strong: C,
+    /// The number of weak references, plus one held collectively by all strong references
+    weak: C,
+    drop_value: unsafe fn(*mut u8),
+    layout: Layout,
+    /// The offset of the value from the start of the allocation
+    offset: usize,
+}
+
+unsafe fn drop_value<U>(value: *mut u8) {
+    ptr::drop_in_place(value as *mut U);
+}
+
+/// A tagged pointer to a reference counted value, used to implement both the strong and weak
+/// handles of [`TaggedRc`] and [`TaggedArc`]. It doesn't manage the counts by itself
+///
+/// [`TaggedRc`]: crate::rc::TaggedRc
+/// [`TaggedArc`]: crate::sync::TaggedArc
+pub(crate) struct RawShared<C, const FREE_BITS: u32, const LOW_BITS: u32, A> {
+    ptr: TaggedPointer<FREE_BITS, LOW_BITS>,
+    _type: PhantomData<(C, A)>,
+}
+
+impl<C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator>
+    RawShared<C, FREE_BITS, LOW_BITS, A>
+{
+    /// Allocates a new value with a strong count of one
+    #[inline]
+    pub(crate) fn new<U>(val: U, discriminant: Discriminant) -> Self {
+        let (layout, offset) = Self::layout::<U>();
+
+        // Safety: The layout always contains the header, so it's never zero sized
+        let base = unsafe { A::alloc(layout) };
+        if base.is_null() {
+            alloc::alloc::handle_alloc_error(layout);
+        }
+
+        // Safety: The allocation was just created with the layout of `U`
+        let value = unsafe { Self::init(base, layout, offset, val) };
+
+        Self {
+            ptr: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(value as *mut (), discriminant),
+            _type: PhantomData,
+        }
+    }
+
+    /// Allocates a new value with a strong count of one, returning an error if the allocation or
+    /// tagging fails
+    #[inline]
+    #[allow(clippy::absurd_extreme_comparisons)]
+    pub(crate) fn try_new<U>(val: U, discriminant: Discriminant) -> Result<Self, TagError> {
+        let max = TaggedPointer::<FREE_BITS, LOW_BITS>::MAX_DISCRIMINANT;
+        if discriminant > max {
+            return Err(TagError::DiscriminantOutOfRange { discriminant, max });
+        }
+
+        let (layout, offset) = Self::layout::<U>();
+
+        // Safety: The layout always contains the header, so it's never zero sized
+        let base = unsafe { A::alloc(layout) };
+        if base.is_null() {
+            return Err(TagError::Alloc(AllocError));
+        }
+
+        // Safety: The allocation was just created with the layout of `U`
+        let value = unsafe { Self::init(base, layout, offset, val) };
+
+        match TaggedPointer::<FREE_BITS, LOW_BITS>::try_from_mut_ptr(value as *mut (), discriminant)
+        {
+            Ok(ptr) => Ok(Self {
+                ptr,
+                _type: PhantomData,
+            }),
+
+            // Safety: The value was just written to the allocation and hasn't been handed out
+            Err(err) => unsafe {
+                ptr::drop_in_place(value);
+                A::dealloc(base, layout);
+
+                Err(err)
+            },
+        }
+    }
+
+    /// The layout of the header followed by a `U` and the offset of the `U`, which is
+    /// aligned to at least `2 ^ LOW_BITS` bytes
+    #[inline]
+    fn layout<U>() -> (Layout, usize) {
+        let value = Layout::new::<U>()
+            .align_to(TaggedPointer::<FREE_BITS, LOW_BITS>::MIN_ALIGNMENT)
+            .expect("`LOW_BITS` is too large for an allocation alignment");
+
+        Layout::new::<Header<C>>()
+            .extend(value)
+            .expect("The shared value is too large to be allocated")
+    }
+
+    /// Writes the header and value into a fresh allocation, returning a pointer to the value
+    ///
+    /// # Safety
+    ///
+    /// `base` must be a live allocation of `layout`, created by [`RawShared::layout`] for `U`
+    #[inline]
+    unsafe fn init<U>(base: *mut u8, layout: Layout, offset: usize, val: U) -> *mut U {
+        let value = base.add(offset);
+
+        // The value's offset is a multiple of both its alignment and the header's, so the header
+        // placed right before it is always aligned, see `Layout::extend`
+        (value.sub(mem::size_of::<Header<C>>()) as *mut Header<C>).write(Header {
+            strong: C::new(1),
+            weak: C::new(1),
+            drop_value: drop_value::<U>,
+            layout,
+            offset,
+        });
+        (value as *mut U).write(val);
+
+        value as *mut U
+    }
+
+    #[inline]
+    fn header(&self) -> &Header<C> {
+        // Safety: The header lives as long as any strong or weak reference to the allocation
+        unsafe { &*(self.value().sub(mem::size_of::<Header<C>>()) as *const Header<C>) }
+    }
+
+    #[inline]
+    pub(crate) fn value(&self) -> *mut u8 {
+        self.ptr.as_mut_ptr()
+    }
+
+    #[inline]
+    pub(crate) fn discriminant(&self) -> Discriminant {
+        self.ptr.discriminant()
+    }
+
+    #[inline]
+    pub(crate) fn ptr(&self) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        self.ptr
+    }
+
+    /// Views the value as a [`TaggedBox`] so that the enum's [`TaggableInner`] impl can be used
+    /// on it, the box must never be dropped
+    ///
+    /// # Safety
+    ///
+    /// The value must be a live variant of `T` stored with the same discriminant
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`TaggableInner`]: crate::TaggableInner
+    #[inline]
+    pub(crate) unsafe fn view<T: TaggableInner<FREE_BITS, LOW_BITS, A>>(
+        &self,
+    ) -> ManuallyDrop<TaggedBox<T, FREE_BITS, LOW_BITS, A>> {
+        ManuallyDrop::new(TaggedBox::from_raw(self.value(), self.discriminant()))
+    }
+
+    #[inline]
+    pub(crate) fn strong_count(&self) -> usize {
+        self.header().strong.load()
+    }
+
+    #[inline]
+    pub(crate) fn weak_count(&self) -> usize {
+        let weak = self.header().weak.load();
+        if weak == LOCKED {
+            // A strong reference is checking uniqueness, which only happens without weak references
+            0
+        } else if self.strong_count() == 0 {
+            // The strong references are gone and took their implicit weak reference with them
+            weak
+        } else {
+            weak - 1
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_unique(&self) -> bool {
+        let header = self.header();
+        C::is_unique(&header.strong, &header.weak)
+    }
+
+    #[inline]
+    pub(crate) fn increment_strong(&self) {
+        self.header().strong.increment();
+    }
+
+    #[inline]
+    pub(crate) fn upgrade(&self) -> bool {
+        self.header().strong.increment_nonzero()
+    }
+
+    #[inline]
+    pub(crate) fn increment_weak(&self) {
+        self.header().weak.increment_weak();
+    }
+
+    /// Releases a strong reference, dropping the value once none are left
+    ///
+    /// # Safety
+    ///
+    /// The caller must own a strong reference and not use it afterwards
+    #[inline]
+    pub(crate) unsafe fn decrement_strong(&self) {
+        if self.header().strong.decrement() {
+            (self.header().drop_value)(self.value());
+
+            // Release the weak reference held by the strong references
+            self.decrement_weak();
+        }
+    }
+
+    /// Releases a weak reference, deallocating the value once none are left
+    ///
+    /// # Safety
+    ///
+    /// The caller must own a weak reference and not use it afterwards
+    #[inline]
+    pub(crate) unsafe fn decrement_weak(&self) {
+        if self.header().weak.decrement() {
+            let (layout, offset) = (self.header().layout, self.header().offset);
+            A::dealloc(self.value().sub(offset), layout);
+        }
+    }
+}
+
+impl<C, const FREE_BITS: u32, const LOW_BITS: u32, A> Clone
+    for RawShared<C, FREE_BITS, LOW_BITS, A>
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, const FREE_BITS: u32, const LOW_BITS: u32, A> Copy
+    for RawShared<C, FREE_BITS, LOW_BITS, A>
+{
+}
+
+/// A reference counted pointer to a tagged value, which is a [`TaggedRc`] or a [`TaggedArc`]
+/// depending on its counter `C`
+///
+/// [`TaggedRc`]: crate::rc::TaggedRc
+/// [`TaggedArc`]: crate::sync::TaggedArc
+pub struct Shared<
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    C: RefCount,
+    const FREE_BITS: u32,
+    const LOW_BITS: u32,
+    A: RawAllocator,
+> {
+    raw: RawShared<C, FREE_BITS, LOW_BITS, A>,
+    // The raw pointer keeps shared pointers from being `Send` or `Sync`, atomically counted ones
+    // implement both when `T` does, see below
+    _type: PhantomData<(T, *const ())>,
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Shared<T, C, FREE_BITS, LOW_BITS, A>
+{
+    /// Creates a new shared pointer from a value and its discriminant
+    ///
+    /// ```rust
+    /// # use tagged_box::{rc::TaggedRc, tagged_box};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
+    /// }
+    ///
+    /// // Safety: `Bricks` stores a `usize` for its first variant
+    /// let red_brick: TaggedRc<Bricks> = unsafe { TaggedRc::new(100_usize, 0) };
+    ///
+    /// unsafe {
+    ///     assert_eq!(red_brick.as_ref::<usize>(), &100);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the allocation fails [`handle_alloc_error`] is called, use [`try_new`] to handle running
+    /// out of memory instead
+    ///
+    /// # Safety
+    ///
+    /// `U` must be the type `T` stores for `discriminant`. The value is compared, hashed, formatted
+    /// and cloned out through `T` by its discriminant alone, and a [`TaggedArc`] is only sent
+    /// between threads by what `T` holds
+    ///
+    /// [`handle_alloc_error`]: https://doc.rust-lang.org/alloc/alloc/fn.handle_alloc_error.html
+    /// [`try_new`]: crate::rc::TaggedRc#try_new
+    /// [`TaggedArc`]: crate::sync::TaggedArc
+    #[inline]
+    pub unsafe fn new<U>(val: U, discriminant: Discriminant) -> Self {
+        Self {
+            raw: RawShared::new(val, discriminant),
+            _type: PhantomData,
+        }
+    }
+
+    /// Creates a new shared pointer from a value and its discriminant, returning an error instead
+    /// of storing a discriminant or pointer that doesn't fit into the tagged pointer or panicking
+    /// when the allocator runs out of memory
+    ///
+    /// # Errors
+    ///
+    /// See [`TaggedBox::try_new`]
+    ///
+    /// # Safety
+    ///
+    /// `U` must be the type `T` stores for `discriminant`, see [`new`]
+    ///
+    /// [`TaggedBox::try_new`]: crate::TaggedBox#try_new
+    /// [`new`]: crate::rc::TaggedRc#new
+    #[inline]
+    pub unsafe fn try_new<U>(val: U, discriminant: Discriminant) -> Result<Self, TagError> {
+        Ok(Self {
+            raw: RawShared::try_new(val, discriminant)?,
+            _type: PhantomData,
+        })
+    }
+
+    /// Returns the discriminant of the pointer
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant {
+        self.raw.discriminant()
+    }
+
+    /// Returns an immutable reference to the shared value
+    ///
+    /// # Safety
+    ///
+    /// The type provided as `U` must be the same type as allocated by `new`
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub unsafe fn as_ref<U>(&self) -> &U {
+        &*self.as_ptr()
+    }
+
+    /// Retrieves a raw pointer to the shared value, which must never be written to
+    #[inline]
+    pub fn as_ptr<U>(&self) -> *const U {
+        self.raw.value() as *const U
+    }
+
+    /// Returns a mutable reference to the value if there are no other strong or [`Weak`]
+    /// pointers to it
+    ///
+    /// ```rust
+    /// # use tagged_box::{rc::TaggedRc, tagged_box};
+    /// # tagged_box! {
+    /// #     struct Brick, enum Bricks {
+    /// #         Red(usize),
+    /// #     }
+    /// # }
+    /// let mut red_brick: TaggedRc<Bricks> = unsafe { TaggedRc::new(100_usize, 0) };
+    ///
+    /// unsafe {
+    ///     *TaggedRc::get_mut::<usize>(&mut red_brick).unwrap() = 200;
+    ///     assert_eq!(red_brick.as_ref::<usize>(), &200);
+    ///
+    ///     let other = red_brick.clone();
+    ///     assert!(TaggedRc::get_mut::<usize>(&mut red_brick).is_none());
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The type provided as `U` must be the same type as allocated by `new`
+    ///
+    /// [`Weak`]: crate::rc::Weak
+    #[inline]
+    pub unsafe fn get_mut<U>(this: &mut Self) -> Option<&mut U> {
+        if this.raw.is_unique() {
+            Some(&mut *(this.raw.value() as *mut U))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value, cloning it into a new allocation first if
+    /// there are any other strong or [`Weak`] pointers to it (clone-on-write)
+    ///
+    /// ```rust
+    /// # use tagged_box::{rc::TaggedRc, tagged_box};
+    /// # tagged_box! {
+    /// #     struct Brick, enum Bricks {
+    /// #         Red(usize),
+    /// #     }
+    /// # }
+    /// let mut red_brick: TaggedRc<Bricks> = unsafe { TaggedRc::new(100_usize, 0) };
+    /// let other = red_brick.clone();
+    ///
+    /// unsafe {
+    ///     *TaggedRc::make_mut::<usize>(&mut red_brick) = 200;
+    ///
+    ///     assert_eq!(red_brick.as_ref::<usize>(), &200);
+    ///     assert_eq!(other.as_ref::<usize>(), &100);
+    /// }
+    /// assert!(!TaggedRc::ptr_eq(&red_brick, &other));
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The type provided as `U` must be the same type as allocated by `new`
+    ///
+    /// [`Weak`]: crate::rc::Weak
+    #[inline]
+    pub unsafe fn make_mut<U: Clone>(this: &mut Self) -> &mut U {
+        if !this.raw.is_unique() {
+            // The clone is stored as the same type with the same discriminant
+            *this = Self::new(this.as_ref::<U>().clone(), this.discriminant());
+        }
+
+        &mut *(this.raw.value() as *mut U)
+    }
+
+    /// Clones the shared value out as its enum
+    #[inline]
+    pub fn to_inner(&self) -> T
+    where
+        T: Clone,
+    {
+        let mut output = None;
+
+        // Safety: The value was allocated as a variant of `T` with its discriminant
+        unsafe {
+            T::ref_from_tagged_box(&self.raw.view(), |this| {
+                output = Some(this.clone());
+            });
+        }
+
+        output.expect("The inner value could not be fetched")
+    }
+
+    /// Creates a new [`Weak`] pointer to the value
+    ///
+    /// [`Weak`]: crate::rc::Weak
+    #[inline]
+    pub fn downgrade(this: &Self) -> Weak<T, C, FREE_BITS, LOW_BITS, A> {
+        this.raw.increment_weak();
+
+        Weak {
+            raw: this.raw,
+            _type: PhantomData,
+        }
+    }
+
+    /// The number of strong pointers to the value
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.raw.strong_count()
+    }
+
+    /// The number of [`Weak`]s pointing to the value
+    ///
+    /// [`Weak`]: crate::rc::Weak
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        this.raw.weak_count()
+    }
+
+    /// Returns true if both pointers point to the same allocation
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.raw.value() == other.raw.value()
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Clone for Shared<T, C, FREE_BITS, LOW_BITS, A>
+{
+    /// Creates another pointer to the same value, which only increments its strong count
+    #[inline]
+    fn clone(&self) -> Self {
+        self.raw.increment_strong();
+
+        Self {
+            raw: self.raw,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Drop for Shared<T, C, FREE_BITS, LOW_BITS, A>
+{
+    fn drop(&mut self) {
+        // Safety: Every shared pointer owns one strong reference
+        unsafe { self.raw.decrement_strong() }
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Debug
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + fmt::Debug + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Safety: The value was allocated as a variant of `T` with its discriminant
+        fmt::Debug::fmt(&*unsafe { self.raw.view::<T>() }, f)
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Display
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + fmt::Display + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Safety: The value was allocated as a variant of `T` with its discriminant
+        fmt::Display::fmt(&*unsafe { self.raw.view::<T>() }, f)
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Pointer
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.raw.ptr(), f)
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> PartialEq
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + PartialEq<T>,
+{
+    fn eq(&self, other: &Shared<T, C, FREE_BITS, LOW_BITS, A>) -> bool {
+        // Safety: Both values were allocated as variants of `T` with their discriminants
+        unsafe { *self.raw.view::<T>() == *other.raw.view::<T>() }
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Eq
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Eq,
+{
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> PartialOrd
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &Shared<T, C, FREE_BITS, LOW_BITS, A>) -> Option<cmp::Ordering> {
+        // Safety: Both values were allocated as variants of `T` with their discriminants
+        unsafe { (*self.raw.view::<T>()).partial_cmp(&*other.raw.view::<T>()) }
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Ord
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Ord,
+{
+    fn cmp(&self, other: &Shared<T, C, FREE_BITS, LOW_BITS, A>) -> cmp::Ordering {
+        // Safety: Both values were allocated as variants of `T` with their discriminants
+        unsafe { (*self.raw.view::<T>()).cmp(&*other.raw.view::<T>()) }
+    }
+}
+
+impl<T, C: RefCount, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Hash
+    for Shared<T, C, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Safety: The value was allocated as a variant of `T` with its discriminant
+        unsafe { (*self.raw.view::<T>()).hash(state) }
+    }
+}
+
+// Safety: Like `Arc`, the value can be dropped or accessed from any thread holding a `TaggedArc`,
+// so `T` has to be both `Send` and `Sync`. The counts are atomic
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Send
+    for Shared<T, AtomicUsize, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Send + Sync,
+{
+}
+
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Sync
+    for Shared<T, AtomicUsize, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Send + Sync,
+{
+}
+
+/// A non-owning pointer to a [`Shared`] pointer's value, which doesn't keep the value alive but
+/// keeps its allocation from being freed
+pub struct Weak<
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    C: RefCount,
+    const FREE_BITS: u32,
+    const LOW_BITS: u32,
+    A: RawAllocator,
+> {
+    raw: RawShared<C, FREE_BITS, LOW_BITS, A>,
+    _type: PhantomData<(T, *const ())>,
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Weak<T, C, FREE_BITS, LOW_BITS, A>
+{
+    /// Attempts to create a strong pointer to the value, returning `None` if it was already dropped
+    ///
+    /// ```rust
+    /// # use tagged_box::{rc::TaggedRc, tagged_box};
+    /// # tagged_box! {
+    /// #     struct Brick, enum Bricks {
+    /// #         Red(usize),
+    /// #     }
+    /// # }
+    /// let red_brick: TaggedRc<Bricks> = unsafe { TaggedRc::new(100_usize, 0) };
+    /// let weak = TaggedRc::downgrade(&red_brick);
+    ///
+    /// assert!(weak.upgrade().is_some());
+    ///
+    /// drop(red_brick);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[inline]
+    pub fn upgrade(&self) -> Option<Shared<T, C, FREE_BITS, LOW_BITS, A>> {
+        if self.raw.upgrade() {
+            Some(Shared {
+                raw: self.raw,
+                _type: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the discriminant of the value, which stays available after it's dropped
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant {
+        self.raw.discriminant()
+    }
+
+    /// The number of strong pointers to the value
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.raw.strong_count()
+    }
+
+    /// The number of `Weak`s pointing to the value
+    #[inline]
+    pub fn weak_count(&self) -> usize {
+        self.raw.weak_count()
+    }
+
+    /// Returns true if both `Weak`s point to the same allocation
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.raw.value() == other.raw.value()
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Clone for Weak<T, C, FREE_BITS, LOW_BITS, A>
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        self.raw.increment_weak();
+
+        Self {
+            raw: self.raw,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Drop for Weak<T, C, FREE_BITS, LOW_BITS, A>
+{
+    fn drop(&mut self) {
+        // Safety: Every `Weak` owns one weak reference
+        unsafe { self.raw.decrement_weak() }
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        C: RefCount,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > fmt::Debug for Weak<T, C, FREE_BITS, LOW_BITS, A>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(Weak)")
+    }
+}
+
+// Safety: A `Weak` can be upgraded into a `TaggedArc` on any thread
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Send
+    for Weak<T, AtomicUsize, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Send + Sync,
+{
+}
+
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Sync
+    for Weak<T, AtomicUsize, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Send + Sync,
+{
+}
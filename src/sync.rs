@@ -0,0 +1,148 @@
+//! Thread safe, atomically reference counted tagged pointers
+//!
+//! A [`TaggedArc`] is the thread safe counterpart of a [`TaggedRc`], using atomic strong and weak
+//! counts so that its value can be shared between threads. It's `Send` and `Sync` only when the
+//! enum it points to is both
+//!
+//! ```rust
+//! use tagged_box::{sync::TaggedArc, tagged_box};
+//! use std::thread;
+//!
+//! tagged_box! {
+//!     #[derive(Debug, Clone, PartialEq)]
+//!     struct Container, enum Item {
+//!         Integer(i32),
+//!         Boolean(bool),
+//!     }
+//! }
+//!
+//! // Safety: `Item` stores an `i32` for its first variant
+//! let shared: TaggedArc<Item> = unsafe { TaggedArc::new(10i32, 0) };
+//! let other = shared.clone();
+//!
+//! let handle = thread::spawn(move || other.to_inner());
+//! assert_eq!(handle.join().unwrap(), Item::Integer(10));
+//! assert_eq!(TaggedArc::strong_count(&shared), 1);
+//! ```
+//!
+//! [`TaggedRc`]: crate::rc::TaggedRc
+
+use crate::{
+    allocator::Global,
+    discriminant::{DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    shared::{self, Shared},
+};
+use core::sync::atomic::AtomicUsize;
+
+/// A thread safe reference counted pointer to a tagged value, see the [module level docs] for more
+///
+/// Like [`TaggedBox`], `T` is the enum the value is a variant of and the type of the value itself
+/// is given by the caller, so the methods that access it are unsafe. The value is dropped once the
+/// last `TaggedArc` is dropped, and its allocation once the last [`Weak`] is
+///
+/// [module level docs]: crate::sync
+/// [`TaggedBox`]: crate::TaggedBox
+/// [`Weak`]: crate::sync::Weak
+pub type TaggedArc<
+    T,
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A = Global,
+> = Shared<T, AtomicUsize, FREE_BITS, LOW_BITS, A>;
+
+/// A non-owning pointer to a [`TaggedArc`]'s value, which doesn't keep the value alive but keeps
+/// its allocation from being freed
+///
+/// [`TaggedArc`]: crate::sync::TaggedArc
+pub type Weak<
+    T,
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A = Global,
+> = shared::Weak<T, AtomicUsize, FREE_BITS, LOW_BITS, A>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tagged_box;
+    use alloc::{string::String, sync::Arc, vec::Vec};
+
+    tagged_box! {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            Text(String),
+            Counted(Arc<()>),
+            Nothing,
+        }
+    }
+
+    #[test]
+    fn counts() {
+        let counter = Arc::new(());
+
+        let shared: TaggedArc<Item> = unsafe { TaggedArc::new(counter.clone(), 1) };
+        let other = shared.clone();
+        let weak = TaggedArc::downgrade(&shared);
+        assert_eq!(TaggedArc::strong_count(&shared), 2);
+        assert_eq!(TaggedArc::weak_count(&shared), 1);
+        assert_eq!(Arc::strong_count(&counter), 2);
+
+        drop(shared);
+        drop(other);
+        assert_eq!(Arc::strong_count(&counter), 1);
+        assert!(weak.upgrade().is_none());
+        assert_eq!(weak.discriminant(), 1);
+    }
+
+    #[test]
+    fn mutation() {
+        let mut shared: TaggedArc<Item> = unsafe { TaggedArc::new(String::from("Hello"), 0) };
+
+        unsafe {
+            TaggedArc::get_mut::<String>(&mut shared)
+                .unwrap()
+                .push_str(", world");
+
+            let weak = TaggedArc::downgrade(&shared);
+            assert!(TaggedArc::get_mut::<String>(&mut shared).is_none());
+            drop(weak);
+
+            let other = shared.clone();
+            TaggedArc::make_mut::<String>(&mut shared).push('!');
+
+            assert_eq!(shared.as_ref::<String>(), "Hello, world!");
+            assert_eq!(other.as_ref::<String>(), "Hello, world");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn threads() {
+        extern crate std;
+
+        let counter = Arc::new(());
+        let shared: TaggedArc<Item> = unsafe { TaggedArc::new(counter.clone(), 1) };
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        let weak = TaggedArc::downgrade(&shared);
+                        let upgraded = weak.upgrade().unwrap();
+                        assert_eq!(upgraded.discriminant(), 1);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(TaggedArc::strong_count(&shared), 1);
+        assert_eq!(TaggedArc::weak_count(&shared), 0);
+        drop(shared);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
@@ -1,6 +1,12 @@
 #![allow(clippy::module_name_repetitions)]
 
-use crate::tagged_box::TaggedBox;
+use crate::{
+    allocator::{Global, RawAllocator},
+    discriminant::{Discriminant, DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    error::AllocError,
+    tagged_box::TaggedBox,
+    variant_type::VariantType,
+};
 
 /// A helper trait for containers that hold a [`TaggedBox`] associated with a specific enum.  
 ///
@@ -18,7 +24,8 @@ pub trait TaggableContainer {
     fn into_inner(self) -> Self::Inner;
 }
 
-/// Represents a value able to be stored in a [`TaggedBox`].  
+/// Represents a value able to be stored in a [`TaggedBox`] with `FREE_BITS` upper and `LOW_BITS` lower bits of discriminant,
+/// allocated with `A`.  
 ///
 /// Using this directly is not recommended, as [`tagged_box!`] should be used instead.  
 /// If you want to implement this yourself, see [`manually implementing a tagged enum`].
@@ -26,19 +33,45 @@ pub trait TaggableContainer {
 /// [`TaggedBox`]: crate::TaggedBox
 /// [`tagged_box!`]: macro.tagged_box.html
 /// [`manually implementing a tagged enum`]: crate::manually_impl_enum
-pub trait TaggableInner: Sized {
+pub trait TaggableInner<
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A: RawAllocator = Global,
+>: Sized
+{
     /// Creates a [`TaggedBox`] from `self`, storing it on the heap and keeping it's discriminant
     /// in the pointer.  
     /// See [`TaggedPointer`] for more
     ///
     /// [`TaggedBox`]: crate::TaggedBox
     /// [`TaggedPointer`]: crate::TaggedPointer
-    fn into_tagged_box(self) -> TaggedBox<Self>;
+    fn into_tagged_box(self) -> TaggedBox<Self, FREE_BITS, LOW_BITS, A>;
+
+    /// Creates a [`TaggedBox`] from `self` like [`into_tagged_box`], but returns an error instead of
+    /// aborting when the value can't be allocated. See [`TaggedBox::try_new`] for more
+    ///
+    /// The default implementation can't fail and forwards to [`into_tagged_box`], [`tagged_box!`]
+    /// generates an implementation that uses [`TaggedBox::try_new`]. Its discriminants are checked
+    /// at compile time, so unlike `TaggedBox::try_new` the allocation is the only thing that can fail
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AllocError`] if the value couldn't be allocated, dropping the value
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`into_tagged_box`]: crate::TaggableInner#into_tagged_box
+    /// [`TaggedBox::try_new`]: crate::TaggedBox#try_new
+    /// [`tagged_box!`]: macro.tagged_box.html
+    /// [`AllocError`]: crate::AllocError
+    #[inline]
+    fn try_into_tagged_box(self) -> Result<TaggedBox<Self, FREE_BITS, LOW_BITS, A>, AllocError> {
+        Ok(self.into_tagged_box())
+    }
 
     /// Creates an instance of `Self` from a [`TaggedBox`], taking ownership of the value
     ///
     /// [`TaggedBox`]: crate::TaggedBox
-    fn from_tagged_box(tagged: TaggedBox<Self>) -> Self;
+    fn from_tagged_box(tagged: TaggedBox<Self, FREE_BITS, LOW_BITS, A>) -> Self;
 
     /// Run a closure on a reference to the value contained in `tagged`
     ///
@@ -46,18 +79,91 @@ pub trait TaggableInner: Sized {
     ///
     /// The closure supplied to `callback` must not move the referenced value
     ///
-    unsafe fn ref_from_tagged_box<F>(tagged: &TaggedBox<Self>, callback: F)
+    unsafe fn ref_from_tagged_box<F>(tagged: &TaggedBox<Self, FREE_BITS, LOW_BITS, A>, callback: F)
     where
         F: FnOnce(&Self);
+
+    /// Runs a closure on a mutable reference to the value contained in `tagged`, storing it back
+    /// into `tagged` afterwards, even if the closure panics
+    ///
+    /// The default implementation moves the value out with [`from_tagged_box`] and allocates it
+    /// again with [`into_tagged_box`]. [`tagged_box!`] generates an implementation that keeps the
+    /// allocation instead when the variant the value is left as has the same size and alignment
+    ///
+    /// [`from_tagged_box`]: crate::TaggableInner#from_tagged_box
+    /// [`into_tagged_box`]: crate::TaggableInner#into_tagged_box
+    /// [`tagged_box!`]: macro.tagged_box.html
+    #[inline]
+    fn mut_from_tagged_box<F>(tagged: &mut TaggedBox<Self, FREE_BITS, LOW_BITS, A>, callback: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        TaggedBox::mutate_reboxed(tagged, callback);
+    }
+
+    /// Clones the value contained in `tagged` into a new [`TaggedBox`] with the same discriminant,
+    /// called from the [`Clone`] implementation of [`TaggedBox`]
+    ///
+    /// The default implementation clones the whole enum through [`ref_from_tagged_box`] and
+    /// allocates it again with [`into_tagged_box`]. [`tagged_box!`] generates an implementation
+    /// that clones the variant straight from its allocation when the enum derives `Clone`
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`Clone`]: https://doc.rust-lang.org/core/clone/trait.Clone.html
+    /// [`ref_from_tagged_box`]: crate::TaggableInner#ref_from_tagged_box
+    /// [`into_tagged_box`]: crate::TaggableInner#into_tagged_box
+    /// [`tagged_box!`]: macro.tagged_box.html
+    #[inline]
+    fn clone_tagged_box(
+        tagged: &TaggedBox<Self, FREE_BITS, LOW_BITS, A>,
+    ) -> TaggedBox<Self, FREE_BITS, LOW_BITS, A>
+    where
+        Self: Clone,
+    {
+        TaggedBox::clone_reboxed(tagged)
+    }
+
+    /// Drops the value contained in `tagged` and frees its allocation, called from the
+    /// [`Drop`] implementation of [`TaggedBox`]. See [`TaggedBox::drop_inner`] for more
+    ///
+    /// # Safety
+    ///
+    /// `tagged` must not be used in any way after this has been called
+    ///
+    /// [`Drop`]: https://doc.rust-lang.org/core/ops/trait.Drop.html
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`TaggedBox::drop_inner`]: crate::TaggedBox#drop_inner
+    unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self, FREE_BITS, LOW_BITS, A>);
+
+    /// Returns the type stored behind a [`TaggedBox`] for `discriminant`, or `None` if it's unknown.
+    /// This is the type table used by [`TaggedBox::downcast_ref`] and friends to safely check the
+    /// type of a value before handing it out
+    ///
+    /// The default implementation knows no types, so downcasts always fail. [`tagged_box!`] generates
+    /// an entry for every variant except `#[inline]` ones, as those aren't stored behind the pointer
+    ///
+    /// Implementations must only return the type that's actually allocated for `discriminant`, the
+    /// same type that [`drop_tagged_box`] drops
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`TaggedBox::downcast_ref`]: crate::TaggedBox#downcast_ref
+    /// [`tagged_box!`]: macro.tagged_box.html
+    /// [`drop_tagged_box`]: crate::TaggableInner#drop_tagged_box
+    #[inline]
+    fn variant_type(discriminant: Discriminant) -> Option<VariantType> {
+        let _ = discriminant;
+        None
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::excessive_precision, clippy::legacy_numeric_constants)]
 mod tests {
     use super::*;
     use crate::tagged_box;
 
     tagged_box! {
-        #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+        #[derive(Debug, Clone, PartialEq, PartialOrd)]
         struct Container, enum Item {
             SingleTuple(usize),
             ManyTuple(usize, usize, f32, usize),
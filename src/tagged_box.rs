@@ -1,24 +1,76 @@
-use crate::{discriminant::Discriminant, taggable::TaggableInner, tagged_pointer::TaggedPointer};
-use alloc::boxed::Box;
+use crate::{
+    allocator::{Global, RawAllocator},
+    discriminant::{Discriminant, DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    error::{AllocError, TagError},
+    immediate::Immediate,
+    provenance,
+    taggable::TaggableInner,
+    tagged_pointer::TaggedPointer,
+    variant_type::VariantType,
+};
 use core::{
     alloc::Layout,
     cmp, fmt,
+    hash::{Hash, Hasher},
     marker::PhantomData,
     mem::{self, ManuallyDrop, MaybeUninit},
-    ptr::NonNull,
+    ptr,
 };
 
 /// A tagged box, associated with a variable type (enum, integer, etc.) able to be extracted from
 /// the underlying [`TaggedPointer`]
 ///
+/// `FREE_BITS` is the number of upper pointer bits used to store the discriminant and `LOW_BITS` the
+/// number of lower ones, see [`TaggedPointer`] for more. When `LOW_BITS` is used, every allocation
+/// is aligned to at least `2 ^ LOW_BITS` bytes so that the lower bits of the pointer are always free
+///
+/// A `TaggedBox` owns its value like a `Box` does, so it's `Send` when `T` is `Send` and `Sync` when
+/// `T` is `Sync`. Every constructor that stores a value, from [`TaggedBox::new`] to
+/// [`TaggedBox::from_raw`], is unsafe and requires the value to be the type `T` stores for its
+/// discriminant, so whatever is behind the pointer is one of the types `T` holds. The containers
+/// generated by [`tagged_box!`] only ever store their own variants
+///
 /// [`TaggedPointer`]: crate::tagged_pointer::TaggedPointer
+/// [`TaggedBox::new`]: crate::TaggedBox#new
+/// [`TaggedBox::from_raw`]: crate::TaggedBox#from_raw
+/// [`tagged_box!`]: macro.tagged_box.html
 #[repr(transparent)]
-pub struct TaggedBox<T> {
-    boxed: TaggedPointer,
-    _type: PhantomData<T>,
+pub struct TaggedBox<
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A: RawAllocator = Global,
+> {
+    boxed: TaggedPointer<FREE_BITS, LOW_BITS>,
+    // Marks the value behind `boxed` as owned, as `TaggedPointer` is always `Send` and `Sync`
+    _type: PhantomData<(T, A)>,
+}
+
+// Safety: The value is owned by the `TaggedBox` and only reachable through it, like a `Box`. Every
+// constructor is unsafe and requires the value to be the type `T` stores for its discriminant, so
+// the value is one of the fields of `T` and is `Send` or `Sync` whenever `T` is. Allocators are
+// stateless, so `A` doesn't matter
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Send
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Send,
+{
+}
+
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Sync
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Sync,
+{
 }
 
-impl<T> TaggedBox<T> {
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > TaggedBox<T, FREE_BITS, LOW_BITS, A>
+{
     /// Creates a new `TaggedBox` from a value and its discriminant
     ///
     /// # Examples
@@ -26,15 +78,18 @@ impl<T> TaggedBox<T> {
     /// ```rust
     /// # extern crate alloc;
     /// # use alloc::{vec::Vec, string::String};
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # struct Paper;
     /// #
-    /// enum Communication {
-    ///     Message(String),
-    ///     Letter(Vec<Paper>),
+    /// tagged_box! {
+    ///     struct Post, enum Communication {
+    ///         Message(String),
+    ///         Letter(Vec<Paper>),
+    ///     }
     /// }
     ///
-    /// let tagged_message: TaggedBox<Communication> = TaggedBox::new(String::from("Foobar is a timeless classic"), 0);
+    /// // Safety: `Communication` stores a `String` for its first variant
+    /// let tagged_message: TaggedBox<Communication> = unsafe { TaggedBox::new(String::from("Foobar is a timeless classic"), 0) };
     /// ```
     ///
     /// ## **Make sure to retrieve the correct type from the `TaggedBox`, or you will encounter undefined behavior!**  
@@ -44,13 +99,15 @@ impl<T> TaggedBox<T> {
     /// ```rust
     /// # extern crate alloc;
     /// # use alloc::{vec::Vec, string::String};
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # struct Paper;
-    /// # enum Communication {
-    /// #     Message(String),
-    /// #     Letter(Vec<Paper>),
+    /// # tagged_box! {
+    /// #     struct Post, enum Communication {
+    /// #         Message(String),
+    /// #         Letter(Vec<Paper>),
+    /// #     }
     /// # }
-    /// # let tagged_message: TaggedBox<Communication> = TaggedBox::new(String::from("Foobar is a timeless classic"), 0);
+    /// # let tagged_message: TaggedBox<Communication> = unsafe { TaggedBox::new(String::from("Foobar is a timeless classic"), 0) };
     /// #
     /// unsafe {
     ///     let message = TaggedBox::into_inner::<String>(tagged_message);
@@ -64,13 +121,15 @@ impl<T> TaggedBox<T> {
     /// ```should_panic
     /// # extern crate alloc;
     /// # use alloc::{vec::Vec, string::String};
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # struct Paper;
-    /// # enum Communication {
-    /// #     Message(String),
-    /// #     Letter(Vec<Paper>),
+    /// # tagged_box! {
+    /// #     struct Post, enum Communication {
+    /// #         Message(String),
+    /// #         Letter(Vec<Paper>),
+    /// #     }
     /// # }
-    /// # let tagged_message: TaggedBox<Communication> = TaggedBox::new(String::from("Foobar is a timeless classic"), 0);
+    /// # let tagged_message: TaggedBox<Communication> = unsafe { TaggedBox::new(String::from("Foobar is a timeless classic"), 0) };
     /// #
     /// unsafe {
     ///     let letter = TaggedBox::into_inner::<Vec<Paper>>(tagged_message); // UB!
@@ -78,27 +137,123 @@ impl<T> TaggedBox<T> {
     /// # panic!("Undefined Behavior!");
     /// ```
     ///
+    /// # Panics
+    ///
+    /// If the allocation fails [`handle_alloc_error`] is called, use [`TaggedBox::try_new`] to
+    /// handle running out of memory instead
+    ///
+    /// # Safety
+    ///
+    /// `U` must be the type `T` stores for `discriminant`, as the box is dropped, cloned and read
+    /// through `T` by its discriminant alone, see [`TaggableInner::variant_type`]
+    ///
+    /// [`handle_alloc_error`]: https://doc.rust-lang.org/alloc/alloc/fn.handle_alloc_error.html
+    /// [`TaggedBox::try_new`]: crate::TaggedBox#try_new
+    /// [`TaggableInner::variant_type`]: crate::TaggableInner#variant_type
     #[inline]
-    pub fn new<U>(val: U, discriminant: Discriminant) -> Self {
+    pub unsafe fn new<U>(val: U, discriminant: Discriminant) -> Self {
         if mem::size_of::<U>() == 0 {
             Self::dangling::<U>(discriminant)
         } else {
-            let layout = Layout::new::<U>();
+            let layout = Self::layout::<U>();
 
             // Safety: The allocation should be properly handled by alloc + layout,
             // and writing should be properly aligned, as the pointer came from the
-            // global allocator, plus the allocated type is not a ZST
-            let ptr = unsafe {
-                let ptr = alloc::alloc::alloc(layout) as *mut U;
-                assert!(ptr as u64 != 0);
-                ptr.write(val);
-
-                ptr
-            };
+            // allocator, plus the allocated type is not a ZST
+            let ptr = A::alloc(layout) as *mut U;
+            if ptr.is_null() {
+                alloc::alloc::handle_alloc_error(layout);
+            }
+            ptr.write(val);
 
             Self {
-                boxed: TaggedPointer::new(ptr as u64, discriminant),
+                boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(
+                    ptr as *mut (),
+                    discriminant,
+                ),
+                _type: PhantomData,
+            }
+        }
+    }
+
+    /// Creates a new `TaggedBox` from a value and its discriminant, returning an error instead
+    /// of storing a discriminant or pointer that doesn't fit into the tagged pointer or panicking
+    /// when the allocator runs out of memory
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox, TagError, TaggedPointer};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
+    /// }
+    ///
+    /// let red_brick = unsafe { TaggedBox::<Bricks>::try_new(100_usize, 0) }.unwrap();
+    /// assert_eq!(red_brick.discriminant(), 0);
+    ///
+    /// let max = <TaggedPointer>::MAX_DISCRIMINANT;
+    /// assert_eq!(
+    ///     unsafe { TaggedBox::<Bricks>::try_new(100_usize, max + 1) }.map(|_| ()),
+    ///     Err(TagError::DiscriminantOutOfRange { discriminant: max + 1, max }),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TagError`] if the discriminant or the allocated pointer can't be stored in a
+    /// [`TaggedPointer`], see [`TaggedPointer::try_new`], or [`TagError::Alloc`] if the allocation
+    /// failed. The value is dropped when an error occurs. The discriminant isn't known at compile
+    /// time here, so unlike the [`AllocError`] of [`TaggableInner::try_into_tagged_box`] the error
+    /// covers tagging the pointer as well
+    ///
+    /// # Safety
+    ///
+    /// `U` must be the type `T` stores for `discriminant`, see [`TaggedBox::new`]
+    ///
+    /// [`TagError`]: crate::TagError
+    /// [`TagError::Alloc`]: crate::TagError::Alloc
+    /// [`TaggedPointer`]: crate::TaggedPointer
+    /// [`TaggedPointer::try_new`]: crate::TaggedPointer#try_new
+    /// [`AllocError`]: crate::AllocError
+    /// [`TaggableInner::try_into_tagged_box`]: crate::TaggableInner#try_into_tagged_box
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
+    #[inline]
+    #[allow(clippy::absurd_extreme_comparisons)]
+    pub unsafe fn try_new<U>(val: U, discriminant: Discriminant) -> Result<Self, TagError> {
+        let max = TaggedPointer::<FREE_BITS, LOW_BITS>::MAX_DISCRIMINANT;
+        if discriminant > max {
+            return Err(TagError::DiscriminantOutOfRange { discriminant, max });
+        }
+
+        if mem::size_of::<U>() == 0 {
+            return Ok(Self::dangling::<U>(discriminant));
+        }
+
+        let layout = Self::layout::<U>();
+
+        // Safety: The allocation should be properly handled by alloc + layout,
+        // and writing should be properly aligned, as the pointer came from the
+        // allocator, plus the allocated type is not a ZST
+        let ptr = A::alloc(layout) as *mut U;
+        if ptr.is_null() {
+            return Err(TagError::Alloc(AllocError));
+        }
+        ptr.write(val);
+
+        match TaggedPointer::<FREE_BITS, LOW_BITS>::try_from_mut_ptr(ptr as *mut (), discriminant) {
+            Ok(boxed) => Ok(Self {
+                boxed,
                 _type: PhantomData,
+            }),
+
+            // Safety: The value was just written to the allocation and hasn't been handed out
+            Err(err) => {
+                ptr::drop_in_place(ptr);
+                A::dealloc(ptr as *mut u8, layout);
+
+                Err(err)
             }
         }
     }
@@ -107,6 +262,7 @@ impl<T> TaggedBox<T> {
     ///
     /// # Safety
     ///
+    /// `U` must be the type `T` stores for `discriminant`, see [`TaggedBox::new`].
     /// `discriminant` must be <= [`MAX_DISCRIMINANT`] and `pointer` must be <=
     /// [`MAX_POINTER_VALUE`].  
     /// See [`TaggedPointer::new_unchecked`] for more
@@ -116,15 +272,17 @@ impl<T> TaggedBox<T> {
     /// ```rust
     /// # extern crate alloc;
     /// # use alloc::{vec::Vec, string::String};
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # struct Paper;
     /// #
-    /// enum Communication {
-    ///     Message(String),
-    ///     Letter(Vec<Paper>),
+    /// tagged_box! {
+    ///     struct Post, enum Communication {
+    ///         Message(String),
+    ///         Letter(Vec<Paper>),
+    ///     }
     /// }
     ///
-    /// let tagged_message: TaggedBox<Communication> = TaggedBox::new(String::from("Foobar is a timeless classic"), 0);
+    /// let tagged_message: TaggedBox<Communication> = unsafe { TaggedBox::new(String::from("Foobar is a timeless classic"), 0) };
     /// ```
     ///
     /// ## **Make sure to retrieve the correct type from the `TaggedBox`, or you will encounter undefined behavior!**  
@@ -134,13 +292,15 @@ impl<T> TaggedBox<T> {
     /// ```rust
     /// # extern crate alloc;
     /// # use alloc::{vec::Vec, string::String};
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # struct Paper;
-    /// # enum Communication {
-    /// #     Message(String),
-    /// #     Letter(Vec<Paper>),
+    /// # tagged_box! {
+    /// #     struct Post, enum Communication {
+    /// #         Message(String),
+    /// #         Letter(Vec<Paper>),
+    /// #     }
     /// # }
-    /// # let tagged_message: TaggedBox<Communication> = TaggedBox::new(String::from("Foobar is a timeless classic"), 0);
+    /// # let tagged_message: TaggedBox<Communication> = unsafe { TaggedBox::new(String::from("Foobar is a timeless classic"), 0) };
     /// #
     /// unsafe {
     ///     let message = TaggedBox::into_inner::<String>(tagged_message);
@@ -154,13 +314,15 @@ impl<T> TaggedBox<T> {
     /// ```should_panic
     /// # extern crate alloc;
     /// # use alloc::{vec::Vec, string::String};
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # struct Paper;
-    /// # enum Communication {
-    /// #     Message(String),
-    /// #     Letter(Vec<Paper>),
+    /// # tagged_box! {
+    /// #     struct Post, enum Communication {
+    /// #         Message(String),
+    /// #         Letter(Vec<Paper>),
+    /// #     }
     /// # }
-    /// # let tagged_message: TaggedBox<Communication> = TaggedBox::new(String::from("Foobar is a timeless classic"), 0);
+    /// # let tagged_message: TaggedBox<Communication> = unsafe { TaggedBox::new(String::from("Foobar is a timeless classic"), 0) };
     /// #
     /// unsafe {
     ///     let letter = TaggedBox::into_inner::<Vec<Paper>>(tagged_message); // UB!
@@ -168,41 +330,66 @@ impl<T> TaggedBox<T> {
     /// # panic!("Undefined Behavior!");
     /// ```
     ///
-    /// [`MAX_DISCRIMINANT`]: crate::discriminant::MAX_DISCRIMINANT
-    /// [`MAX_POINTER_VALUE`]: crate::discriminant::MAX_POINTER_VALUE
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    /// [`MAX_POINTER_VALUE`]: crate::TaggedPointer::MAX_POINTER_VALUE
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
     /// [`TaggedPointer::new_unchecked`]: crate::tagged_pointer::TaggedPointer#new_unchecked
     #[inline]
     pub unsafe fn new_unchecked<U>(val: U, discriminant: Discriminant) -> Self {
         if mem::size_of::<U>() == 0 {
             Self::dangling::<U>(discriminant)
         } else {
-            let layout = Layout::new::<U>();
+            let layout = Self::layout::<U>();
 
             // Safety: The allocation should be properly handled by alloc + layout,
             // and writing should be properly aligned, as the pointer came from the
-            // global allocator
+            // allocator
             let ptr = {
-                let ptr = alloc::alloc::alloc(layout) as *mut U;
-                assert!(ptr as u64 != 0);
+                let ptr = A::alloc(layout) as *mut U;
+                if ptr.is_null() {
+                    alloc::alloc::handle_alloc_error(layout);
+                }
                 ptr.write(val);
 
                 ptr
             };
 
             Self {
-                boxed: TaggedPointer::new(ptr as u64, discriminant),
+                boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(
+                    ptr as *mut (),
+                    discriminant,
+                ),
                 _type: PhantomData,
             }
         }
     }
 
+    /// The layout used to allocate a `U`, aligned to at least `2 ^ LOW_BITS` bytes
+    #[inline]
+    fn layout<U>() -> Layout {
+        Self::aligned_layout(Layout::new::<U>())
+    }
+
+    /// Aligns `layout` to at least `2 ^ LOW_BITS` bytes
+    #[inline]
+    fn aligned_layout(layout: Layout) -> Layout {
+        layout
+            .align_to(TaggedPointer::<FREE_BITS, LOW_BITS>::MIN_ALIGNMENT)
+            .expect("`LOW_BITS` is too large for an allocation alignment")
+    }
+
     /// Creates a dangling tagged box, see [`NonNull::dangling`] for more information
     ///
+    /// # Safety
+    ///
+    /// `T` must store a zero sized `U` or nothing at all for `discriminant`, as dropping the box
+    /// drops and frees whatever `T` stores for it
+    ///
     /// [`NonNull::dangling`]: https://doc.rust-lang.org/core/ptr/struct.NonNull.html#method.dangling
     #[inline]
-    pub const fn dangling<U>(discriminant: Discriminant) -> Self {
+    pub const unsafe fn dangling<U>(discriminant: Discriminant) -> Self {
         Self {
-            boxed: TaggedPointer::dangling::<U>(discriminant),
+            boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::dangling::<U>(discriminant),
             _type: PhantomData,
         }
     }
@@ -217,12 +404,14 @@ impl<T> TaggedBox<T> {
     /// # Example
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// enum Bricks {
-    ///     Red(usize),
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
     /// }
     ///
-    /// let red_brick: TaggedBox<Bricks> = TaggedBox::new(100_usize, 0);
+    /// let red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
     ///
     /// unsafe {
     ///     // We allocated a usize, so we can retrieve one
@@ -246,12 +435,14 @@ impl<T> TaggedBox<T> {
     /// # Example
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// enum Bricks {
-    ///     Red(usize),
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
     /// }
     ///
-    /// let mut red_brick: TaggedBox<Bricks> = TaggedBox::new(100_usize, 0);
+    /// let mut red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
     ///
     /// unsafe {
     ///     assert_eq!(red_brick.as_ref::<usize>(), &100);
@@ -277,12 +468,14 @@ impl<T> TaggedBox<T> {
     /// # Example
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// enum Bricks {
-    ///     Red(usize),
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
     /// }
     ///
-    /// let red_brick: TaggedBox<Bricks> = TaggedBox::new(100_usize, 0);
+    /// let red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
     ///
     /// unsafe {
     ///     // We allocated a usize, so we can retrieve one
@@ -296,7 +489,54 @@ impl<T> TaggedBox<T> {
     #[must_use]
     pub unsafe fn into_inner<U>(tagged: Self) -> U {
         let mut tagged = ManuallyDrop::new(tagged);
-        tagged.as_mut_ptr::<U>().read()
+        let ptr = tagged.as_mut_ptr::<U>();
+        let value = ptr.read();
+
+        // Safety: The value has been moved out of the allocation, so only the
+        // backing memory is left to be freed
+        if mem::size_of::<U>() != 0 {
+            A::dealloc(ptr as *mut u8, Self::layout::<U>());
+        }
+
+        value
+    }
+
+    /// Drops the value stored on the heap in place and frees its allocation, used to implement
+    /// [`TaggableInner::drop_tagged_box`]
+    ///
+    /// # Safety
+    ///
+    /// The type provided as `U` must be the same type as allocated by `new`, and `tagged`
+    /// must not be used in any way after this has been called, including being dropped
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # use core::mem::ManuallyDrop;
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
+    /// }
+    ///
+    /// let mut red_brick = ManuallyDrop::new(unsafe { TaggedBox::<Bricks>::new(100_usize, 0) });
+    ///
+    /// unsafe {
+    ///     // We allocated a usize, so we can drop one
+    ///     TaggedBox::<Bricks>::drop_inner::<usize>(&mut red_brick);
+    /// }
+    /// ```
+    ///
+    /// [`TaggableInner::drop_tagged_box`]: crate::TaggableInner#drop_tagged_box
+    #[inline]
+    pub unsafe fn drop_inner<U>(tagged: &mut Self) {
+        let ptr = tagged.as_mut_ptr::<U>();
+        ptr::drop_in_place(ptr);
+
+        if mem::size_of::<U>() != 0 {
+            A::dealloc(ptr as *mut u8, Self::layout::<U>());
+        }
     }
 
     /// Consumes the `TaggedBox`, returning a raw pointer.
@@ -307,10 +547,14 @@ impl<T> TaggedBox<T> {
     /// # Example
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// # enum InnerValue {}
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Bytes([u8; 10]),
+    /// #     }
+    /// # }
     /// #
-    /// let tagged_box: TaggedBox<InnerValue> = TaggedBox::new([10u8; 10], 8);
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::new([10u8; 10], 0) };
     ///
     /// // Get the raw pointer to the heap-allocated value
     /// let raw: *mut [u8; 10] = TaggedBox::into_raw(tagged_box);
@@ -325,8 +569,46 @@ impl<T> TaggedBox<T> {
         ManuallyDrop::new(tagged).boxed.as_mut_ptr()
     }
 
-    /// Creates a [`Box`] from the provided `TaggedBox`  
-    /// Trusts that the type provided as `U` is valid for the allocated layout.
+    /// Creates a `TaggedBox` that owns whatever `boxed` points to, keeping inline values intact
+    #[inline]
+    pub(crate) const fn from_tagged_pointer(boxed: TaggedPointer<FREE_BITS, LOW_BITS>) -> Self {
+        Self {
+            boxed,
+            _type: PhantomData,
+        }
+    }
+
+    /// Consumes the `TaggedBox` without dropping its value, returning its tagged pointer
+    #[inline]
+    pub(crate) fn into_tagged_pointer(tagged: Self) -> TaggedPointer<FREE_BITS, LOW_BITS> {
+        ManuallyDrop::new(tagged).boxed
+    }
+
+    /// Clones the value in `tagged` as its enum with [`TaggableInner::ref_from_tagged_box`] and
+    /// allocates the clone with [`TaggableInner::into_tagged_box`]
+    ///
+    /// [`TaggableInner::ref_from_tagged_box`]: crate::TaggableInner#ref_from_tagged_box
+    /// [`TaggableInner::into_tagged_box`]: crate::TaggableInner#into_tagged_box
+    pub(crate) fn clone_reboxed(tagged: &Self) -> Self
+    where
+        T: Clone,
+    {
+        let mut output = None;
+        unsafe {
+            T::ref_from_tagged_box(tagged, |this| {
+                output = Some(this.clone());
+            });
+        }
+
+        output
+            .expect("The inner value could not be fetched")
+            .into_tagged_box()
+    }
+
+    /// Consumes and leaks the `TaggedBox`, returning a mutable reference to its value that
+    /// lives for as long as `'a`, see [`Box::leak`]
+    ///
+    /// The memory can be reclaimed by passing the reference to [`TaggedBox::from_raw`]
     ///
     /// # Safety
     ///
@@ -336,50 +618,54 @@ impl<T> TaggedBox<T> {
     /// # Example
     ///
     /// ```rust
-    /// # extern crate alloc;
-    /// # use alloc::boxed::Box;
-    /// # use tagged_box::TaggedBox;
-    /// # enum InnerValue {}
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Number(usize),
+    /// #     }
+    /// # }
     /// #
-    /// let tagged_box: TaggedBox<InnerValue> = TaggedBox::new([10u8; 10], 8);
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::new(100_usize, 0) };
     ///
-    /// unsafe {
-    ///     // Get the Boxed value
-    ///     let boxed: Box<[u8; 10]> = TaggedBox::into_box(tagged_box);
-    /// }
+    /// let leaked: &'static mut usize = unsafe { TaggedBox::leak(tagged_box) };
+    /// *leaked += 1;
+    ///
+    /// assert_eq!(*leaked, 101);
     /// ```
     ///
-    /// [`Box`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html
+    /// [`Box::leak`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html#method.leak
+    /// [`TaggedBox::from_raw`]: crate::TaggedBox#from_raw
     #[inline]
-    pub unsafe fn into_box<U>(tagged: Self) -> alloc::boxed::Box<U> {
-        use alloc::boxed::Box;
-
-        let raw = Self::into_raw(tagged);
-        Box::from_raw(raw)
+    pub unsafe fn leak<'a, U>(tagged: Self) -> &'a mut U {
+        &mut *Self::into_raw(tagged)
     }
 
-    // TODO: from_box
-
     /// Constructs a `TaggedBox` from a raw pointer and a discriminant.
     ///
     /// Trusts that the provided pointer is valid and non-null, as well as that the memory
     /// allocated is the method same as allocated by `TaggedBox`. `TaggedBox` uses the standard
-    /// allocator
+    /// allocator, aligning allocations to at least `2 ^ LOW_BITS` bytes
     ///
     /// # Safety
     ///
     /// This function is unsafe because improper use may lead to memory problems.
     /// For example, a double-free may occur if the function is called twice on the same raw pointer.
     ///
+    /// `U` must also be the type `T` stores for `discriminant`, see [`TaggedBox::new`]
+    ///
     /// # Example
     ///
     /// ```rust
     /// # extern crate alloc;
-    /// # use tagged_box::TaggedBox;
+    /// # use tagged_box::{tagged_box, TaggedBox};
     /// # use alloc::{vec, vec::Vec};
-    /// # enum InnerValue {}
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Numbers(Vec<usize>),
+    /// #     }
+    /// # }
     /// #
-    /// let tagged_box: TaggedBox<InnerValue> = TaggedBox::new(vec![100_usize, 200, 300], 10);
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::new(vec![100_usize, 200, 300], 0) };
     ///
     /// // Turn the tagged box into a raw pointer and its discriminant
     /// let discriminant = tagged_box.discriminant();
@@ -394,43 +680,141 @@ impl<T> TaggedBox<T> {
     /// }
     /// ```
     ///
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
     #[inline]
     pub unsafe fn from_raw<U>(raw: *mut U, discriminant: Discriminant) -> Self {
         Self {
-            boxed: TaggedPointer::new(raw as u64, discriminant),
+            boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(raw as *mut (), discriminant),
             _type: PhantomData,
         }
     }
 
+    /// Constructs a `TaggedBox` from a raw pointer and a discriminant, returning an error if
+    /// either can't be stored in a [`TaggedPointer`]
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`TaggedBox::from_raw`] apply, the only difference is that the
+    /// discriminant and pointer are checked
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TagError`] if the discriminant or pointer can't be stored in a [`TaggedPointer`],
+    /// see [`TaggedPointer::try_new`]. Ownership of `raw` stays with the caller when an error occurs
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Integer(usize),
+    /// #     }
+    /// # }
+    /// #
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::new(100_usize, 0) };
+    /// let raw: *mut usize = TaggedBox::into_raw(tagged_box);
+    ///
+    /// unsafe {
+    ///     let tagged_box: TaggedBox<InnerValue> = TaggedBox::try_from_raw(raw, 0).unwrap();
+    ///
+    ///     assert_eq!(TaggedBox::into_inner::<usize>(tagged_box), 100);
+    /// }
+    /// ```
+    ///
+    /// [`TagError`]: crate::TagError
+    /// [`TaggedPointer`]: crate::TaggedPointer
+    /// [`TaggedPointer::try_new`]: crate::TaggedPointer#try_new
+    /// [`TaggedBox::from_raw`]: crate::TaggedBox#from_raw
+    #[inline]
+    pub unsafe fn try_from_raw<U>(
+        raw: *mut U,
+        discriminant: Discriminant,
+    ) -> Result<Self, TagError> {
+        TaggedPointer::<FREE_BITS, LOW_BITS>::try_from_mut_ptr(raw as *mut (), discriminant).map(
+            |boxed| Self {
+                boxed,
+                _type: PhantomData,
+            },
+        )
+    }
+
     /// Fetches the discriminant of a `TaggedBox`
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// # enum InnerValue {}
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Nothing,
+    /// #         Integer(i32),
+    /// #     }
+    /// # }
     /// #
-    /// let tagged_box: TaggedBox<InnerValue> = TaggedBox::new(0x00, 11);
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::new(0x00, 1) };
     ///
-    /// assert_eq!(tagged_box.discriminant(), 11);
+    /// assert_eq!(tagged_box.discriminant(), 1);
     /// ```
     ///
     #[inline]
-    pub const fn discriminant(&self) -> Discriminant {
+    pub fn discriminant(&self) -> Discriminant {
         self.boxed.discriminant()
     }
 
+    /// Changes the discriminant of the `TaggedBox` in place without re-allocating, reinterpreting
+    /// the boxed value as the variant that `discriminant` belongs to
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Number, enum Numbers {
+    ///         Signed(i32),
+    ///         Unsigned(u32),
+    ///     }
+    /// }
+    ///
+    /// let mut tagged: TaggedBox<Numbers> = unsafe { TaggedBox::new(-1i32, 0) };
+    ///
+    /// // Safety: `i32` and `u32` have the same layout and every bit pattern is a valid `u32`
+    /// unsafe {
+    ///     tagged.set_discriminant(1);
+    ///
+    ///     assert_eq!(TaggedBox::into_inner::<u32>(tagged), u32::MAX);
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// - `discriminant` must be a valid discriminant of `T`
+    /// - The type of the new variant must have the same size and alignment as the type of the
+    ///   current one, as the allocation will be read, dropped and deallocated as the new type
+    /// - The current value must be a valid instance of the new variant's type, for example
+    ///   through both types being `#[repr(transparent)]` wrappers of the same type
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `discriminant` is greater than [`MAX_DISCRIMINANT`]
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    #[inline]
+    pub unsafe fn set_discriminant(&mut self, discriminant: Discriminant) {
+        self.boxed.set_discriminant(discriminant);
+    }
+
     /// Retrieves a raw pointer to the data owned by `TaggedBox`, see [`TaggedPointer::as_ptr`]  
     /// The caller must ensure that the returned pointer is never written to. If you need to
     /// mutate the contents of the tagged pointer, use [`as_mut_ptr`].
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// enum Bricks {
-    ///     Red(usize),
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
     /// }
     ///
-    /// let red_brick: TaggedBox<Bricks> = TaggedBox::new(100_usize, 0);
+    /// let red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
     ///
     /// unsafe {
     ///     assert_eq!(*red_brick.as_ptr::<usize>(), 100);
@@ -440,8 +824,8 @@ impl<T> TaggedBox<T> {
     /// [`TaggedPointer::as_ptr`]: crate::TaggedPointer#as_ptr
     /// [`as_mut_ptr`]: crate::TaggedBox::as_ptr
     #[inline]
-    pub const fn as_ptr<U>(&self) -> *const U {
-        self.boxed.as_ptr() as *const U
+    pub fn as_ptr<U>(&self) -> *const U {
+        self.boxed.as_ptr()
     }
 
     /// Retrieves a raw pointer to the data owned by `TaggedBox`, see [`TaggedPointer::as_mut_ptr`]  
@@ -451,12 +835,14 @@ impl<T> TaggedBox<T> {
     /// # Example
     ///
     /// ```rust
-    /// # use tagged_box::TaggedBox;
-    /// enum Bricks {
-    ///     Red(usize),
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
     /// }
     ///
-    /// let mut red_brick: TaggedBox<Bricks> = TaggedBox::new(100_usize, 0);
+    /// let mut red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
     ///
     /// unsafe {
     ///     *red_brick.as_mut_ptr::<usize>() = 100_000;
@@ -468,76 +854,1130 @@ impl<T> TaggedBox<T> {
     /// [`TaggedPointer::as_mut_ptr`]: crate::TaggedPointer#as_mut_ptr
     #[inline]
     pub fn as_mut_ptr<U>(&mut self) -> *mut U {
-        self.boxed.as_mut_ptr() as *mut U
+        self.boxed.as_mut_ptr()
     }
 
     /// Retrieves a u64 pointing to the data owned by `TaggedBox`, see [`TaggedPointer::as_usize`]
     ///
     /// [`TaggedPointer::as_u64`]: crate::TaggedPointer#as_u64
     #[inline]
-    pub(crate) const fn as_u64(&self) -> u64 {
+    pub(crate) fn as_u64(&self) -> u64 {
         self.boxed.as_u64()
     }
 }
 
-impl<T> fmt::Debug for TaggedBox<T>
-where
-    T: TaggableInner + fmt::Debug + Clone,
+/// Conversions to and from [`Box`], which are only available for the global allocator as a
+/// `Box`'s allocation can only be shared with a `TaggedBox` that uses the same allocator
+///
+/// [`Box`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html
+impl<T: TaggableInner<FREE_BITS, LOW_BITS>, const FREE_BITS: u32, const LOW_BITS: u32>
+    TaggedBox<T, FREE_BITS, LOW_BITS>
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut result = Ok(());
-        unsafe {
-            T::ref_from_tagged_box(self, |this| {
-                result = write!(f, "{:?}", this);
-            });
-        }
-
-        result
-    }
-}
+    /// Creates a [`Box`] from the provided `TaggedBox`  
+    /// Trusts that the type provided as `U` is valid for the allocated layout.
+    ///
+    /// # Safety
+    ///
+    /// The type provided as `U` must be the same type that the instance of `TaggedBox`
+    /// was initialized with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::boxed::Box;
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Bytes([u8; 10]),
+    /// #     }
+    /// # }
+    /// #
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::new([10u8; 10], 0) };
+    ///
+    /// unsafe {
+    ///     // Get the Boxed value
+    ///     let boxed: Box<[u8; 10]> = TaggedBox::into_box(tagged_box);
+    /// }
+    /// ```
+    ///
+    /// [`Box`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html
+    #[inline]
+    pub unsafe fn into_box<U>(tagged: Self) -> alloc::boxed::Box<U> {
+        use alloc::boxed::Box;
 
-impl<T> fmt::Display for TaggedBox<T>
-where
-    T: TaggableInner + fmt::Display + Clone,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut result = Ok(());
-        unsafe {
-            T::ref_from_tagged_box(self, |this| {
-                result = write!(f, "{}", this);
-            });
+        // If the allocation was over-aligned to make room for the lower bits, it can't be
+        // given to a `Box` directly and has to be moved into one instead
+        if Self::layout::<U>() == Layout::new::<U>() {
+            let raw = Self::into_raw(tagged);
+            Box::from_raw(raw)
+        } else {
+            Box::new(Self::into_inner(tagged))
         }
-
-        result
     }
-}
 
-impl<T> Clone for TaggedBox<T>
-where
-    T: TaggableInner + Clone,
-{
-    fn clone(&self) -> Self {
-        let mut output = None;
-        unsafe {
-            T::ref_from_tagged_box(self, |this| {
-                output = Some(this.clone());
-            });
-        }
+    /// Creates a `TaggedBox` from a [`Box`] and a discriminant, reusing the box's allocation
+    /// when it's aligned to at least `2 ^ LOW_BITS` bytes, see [`TaggedBox::into_box`] for the reverse
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::boxed::Box;
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Container, enum InnerValue {
+    /// #         Bytes([u8; 10]),
+    /// #     }
+    /// # }
+    /// #
+    /// let boxed = Box::new([10u8; 10]);
+    /// let tagged_box: TaggedBox<InnerValue> = unsafe { TaggedBox::from_box(boxed, 0) };
+    ///
+    /// unsafe {
+    ///     let boxed: Box<[u8; 10]> = TaggedBox::into_box(tagged_box);
+    ///     assert_eq!(*boxed, [10; 10]);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics under the same conditions as [`TaggedBox::new`]
+    ///
+    /// # Safety
+    ///
+    /// `U` must be the type `T` stores for `discriminant`, see [`TaggedBox::new`]
+    ///
+    /// [`Box`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html
+    /// [`TaggedBox::into_box`]: crate::TaggedBox#into_box
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
+    #[inline]
+    pub unsafe fn from_box<U>(boxed: alloc::boxed::Box<U>, discriminant: Discriminant) -> Self {
+        use alloc::boxed::Box;
 
-        output
-            .expect("The inner value could not be fetched")
-            .into_tagged_box()
+        // Allocations that aren't aligned enough to hold the lower bits have to be moved into
+        // one that is, the same as in `into_box`
+        if Self::layout::<U>() == Layout::new::<U>() {
+            // Safety: The box was allocated by the global allocator with the layout of `U`,
+            // which is the same layout that `TaggedBox` uses for it
+            Self::from_raw(Box::into_raw(boxed), discriminant)
+        } else {
+            Self::new(*boxed, discriminant)
+        }
     }
 }
 
-impl<T> Copy for TaggedBox<T> where T: TaggableInner + Copy {}
-
-impl<T> PartialEq for TaggedBox<T>
-where
-    T: TaggableInner + PartialEq<T>,
-{
-    fn eq(&self, other: &TaggedBox<T>) -> bool {
-        let mut eq = false;
+/// Unsized payloads, such as slices, `str`s and trait objects. The allocation starts with a header
+/// holding the fat pointer to the payload that follows it, so the `TaggedBox` itself stays a single
+/// thin pointer and the payload is only ever one indirection away
+///
+/// `tagged_box!` has no unsized variants, so unsized payloads are for enums that implement
+/// [`TaggableInner`] by hand and free them with [`drop_inner_unsized`]. A `tagged_box!` variant can
+/// still hold a `Box<str>` or another boxed unsized value, at the cost of a second allocation
+///
+/// ```rust
+/// extern crate alloc;
+/// use alloc::{boxed::Box, string::ToString};
+/// use core::fmt::Display;
+/// use tagged_box::{TaggableInner, TaggedBox};
+///
+/// enum Payload {
+///     Numbers(Box<[i32]>),
+///     Text(Box<str>),
+///     Shown(Box<dyn Display>),
+/// }
+///
+/// impl TaggableInner for Payload {
+///     fn into_tagged_box(self) -> TaggedBox<Self> {
+///         // Safety: Every discriminant is dropped as the unsized type it's created with
+///         unsafe {
+///             match self {
+///                 Self::Numbers(numbers) => TaggedBox::from_box_dyn(numbers, 0),
+///                 Self::Text(text) => TaggedBox::from_box_dyn(text, 1),
+///                 Self::Shown(shown) => TaggedBox::from_box_dyn(shown, 2),
+///             }
+///         }
+///     }
+///
+///     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+///         unsafe {
+///             match tagged.discriminant() {
+///                 0 => Self::Numbers(TaggedBox::into_box_unsized(tagged)),
+///                 1 => Self::Text(TaggedBox::into_box_unsized(tagged)),
+///                 2 => Self::Shown(TaggedBox::into_box_unsized(tagged)),
+///                 _ => unreachable!(),
+///             }
+///         }
+///     }
+///
+///     unsafe fn ref_from_tagged_box<F: FnOnce(&Self)>(tagged: &TaggedBox<Self>, callback: F) {
+///         // Unsized values can't be read out of the box bitwise, so the callback gets a copy
+///         match tagged.discriminant() {
+///             0 => callback(&Self::Numbers(Box::from(tagged.as_unsized_ref::<[i32]>()))),
+///             1 => callback(&Self::Text(Box::from(tagged.as_unsized_ref::<str>()))),
+///             2 => {
+///                 let shown = tagged.as_unsized_ref::<dyn Display>().to_string();
+///                 callback(&Self::Shown(Box::new(shown)))
+///             }
+///             _ => unreachable!(),
+///         }
+///     }
+///
+///     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+///         match tagged.discriminant() {
+///             0 => TaggedBox::drop_inner_unsized::<[i32]>(tagged),
+///             1 => TaggedBox::drop_inner_unsized::<str>(tagged),
+///             2 => TaggedBox::drop_inner_unsized::<dyn Display>(tagged),
+///             _ => unreachable!(),
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`TaggableInner`]: crate::TaggableInner
+/// [`drop_inner_unsized`]: crate::TaggedBox#drop_inner_unsized
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > TaggedBox<T, FREE_BITS, LOW_BITS, A>
+{
+    /// Creates a new `TaggedBox` holding a clone of every element of `slice`
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::{boxed::Box, string::ToString};
+    /// # use core::fmt::Display;
+    /// # use tagged_box::{TaggableInner, TaggedBox};
+    /// #
+    /// # enum Payload {
+    /// #     Numbers(Box<[i32]>),
+    /// #     Text(Box<str>),
+    /// #     Shown(Box<dyn Display>),
+    /// # }
+    /// #
+    /// # impl TaggableInner for Payload {
+    /// #     fn into_tagged_box(self) -> TaggedBox<Self> {
+    /// #         // Safety: Every discriminant is dropped as the unsized type it's created with
+    /// #         unsafe {
+    /// #             match self {
+    /// #                 Self::Numbers(numbers) => TaggedBox::from_box_dyn(numbers, 0),
+    /// #                 Self::Text(text) => TaggedBox::from_box_dyn(text, 1),
+    /// #                 Self::Shown(shown) => TaggedBox::from_box_dyn(shown, 2),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+    /// #         unsafe {
+    /// #             match tagged.discriminant() {
+    /// #                 0 => Self::Numbers(TaggedBox::into_box_unsized(tagged)),
+    /// #                 1 => Self::Text(TaggedBox::into_box_unsized(tagged)),
+    /// #                 2 => Self::Shown(TaggedBox::into_box_unsized(tagged)),
+    /// #                 _ => unreachable!(),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn ref_from_tagged_box<F: FnOnce(&Self)>(tagged: &TaggedBox<Self>, callback: F) {
+    /// #         // Unsized values can't be read out of the box bitwise, so the callback gets a copy
+    /// #         match tagged.discriminant() {
+    /// #             0 => callback(&Self::Numbers(Box::from(tagged.as_unsized_ref::<[i32]>()))),
+    /// #             1 => callback(&Self::Text(Box::from(tagged.as_unsized_ref::<str>()))),
+    /// #             2 => {
+    /// #                 let shown = tagged.as_unsized_ref::<dyn Display>().to_string();
+    /// #                 callback(&Self::Shown(Box::new(shown)))
+    /// #             }
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+    /// #         match tagged.discriminant() {
+    /// #             0 => TaggedBox::drop_inner_unsized::<[i32]>(tagged),
+    /// #             1 => TaggedBox::drop_inner_unsized::<str>(tagged),
+    /// #             2 => TaggedBox::drop_inner_unsized::<dyn Display>(tagged),
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// # }
+    /// // Safety: `Payload` stores and drops a `[i32]` for its first variant
+    /// let tagged = unsafe { TaggedBox::<Payload>::from_slice(&[1, 2, 3], 0) };
+    ///
+    /// unsafe {
+    ///     assert_eq!(tagged.as_unsized_ref::<[i32]>(), &[1, 2, 3]);
+    /// }
+    /// ```
+    ///
+    /// If cloning an element panics the allocation and the already cloned elements are leaked
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics under the same conditions as [`TaggedBox::new`]
+    ///
+    /// # Safety
+    ///
+    /// `T` must store a `[U]` for `discriminant`, dropping it with [`drop_inner_unsized`] in its
+    /// [`TaggableInner::drop_tagged_box`] and giving it no entry in its [`variant_type`] table.
+    /// Otherwise `T` would free it as a sized type with the wrong layout when the box is dropped,
+    /// which can happen during a panic even if the value is meant to be moved out with
+    /// [`into_box_unsized`]
+    ///
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
+    /// [`drop_inner_unsized`]: crate::TaggedBox#drop_inner_unsized
+    /// [`TaggableInner::drop_tagged_box`]: crate::TaggableInner#drop_tagged_box
+    /// [`variant_type`]: crate::TaggableInner#variant_type
+    /// [`into_box_unsized`]: crate::TaggedBox#into_box_unsized
+    #[inline]
+    pub unsafe fn from_slice<U: Clone>(slice: &[U], discriminant: Discriminant) -> Self {
+        let value = Layout::array::<U>(slice.len()).expect("The slice is too large to allocate");
+
+        // Safety: The payload is `slice.len()` initialized elements of `U`, matching `value`
+        Self::new_unsized(value, discriminant, |payload| {
+            let elements = payload as *mut U;
+            for (idx, element) in slice.iter().enumerate() {
+                elements.add(idx).write(element.clone());
+            }
+
+            ptr::slice_from_raw_parts_mut(elements, slice.len())
+        })
+    }
+
+    /// Creates a new `TaggedBox` holding a copy of `string`
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::{boxed::Box, string::ToString};
+    /// # use core::fmt::Display;
+    /// # use tagged_box::{TaggableInner, TaggedBox};
+    /// #
+    /// # enum Payload {
+    /// #     Numbers(Box<[i32]>),
+    /// #     Text(Box<str>),
+    /// #     Shown(Box<dyn Display>),
+    /// # }
+    /// #
+    /// # impl TaggableInner for Payload {
+    /// #     fn into_tagged_box(self) -> TaggedBox<Self> {
+    /// #         // Safety: Every discriminant is dropped as the unsized type it's created with
+    /// #         unsafe {
+    /// #             match self {
+    /// #                 Self::Numbers(numbers) => TaggedBox::from_box_dyn(numbers, 0),
+    /// #                 Self::Text(text) => TaggedBox::from_box_dyn(text, 1),
+    /// #                 Self::Shown(shown) => TaggedBox::from_box_dyn(shown, 2),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+    /// #         unsafe {
+    /// #             match tagged.discriminant() {
+    /// #                 0 => Self::Numbers(TaggedBox::into_box_unsized(tagged)),
+    /// #                 1 => Self::Text(TaggedBox::into_box_unsized(tagged)),
+    /// #                 2 => Self::Shown(TaggedBox::into_box_unsized(tagged)),
+    /// #                 _ => unreachable!(),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn ref_from_tagged_box<F: FnOnce(&Self)>(tagged: &TaggedBox<Self>, callback: F) {
+    /// #         // Unsized values can't be read out of the box bitwise, so the callback gets a copy
+    /// #         match tagged.discriminant() {
+    /// #             0 => callback(&Self::Numbers(Box::from(tagged.as_unsized_ref::<[i32]>()))),
+    /// #             1 => callback(&Self::Text(Box::from(tagged.as_unsized_ref::<str>()))),
+    /// #             2 => {
+    /// #                 let shown = tagged.as_unsized_ref::<dyn Display>().to_string();
+    /// #                 callback(&Self::Shown(Box::new(shown)))
+    /// #             }
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+    /// #         match tagged.discriminant() {
+    /// #             0 => TaggedBox::drop_inner_unsized::<[i32]>(tagged),
+    /// #             1 => TaggedBox::drop_inner_unsized::<str>(tagged),
+    /// #             2 => TaggedBox::drop_inner_unsized::<dyn Display>(tagged),
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// # }
+    /// // Safety: `Payload` stores and drops a `str` for its second variant
+    /// let tagged = unsafe { TaggedBox::<Payload>::from_str("Hello from tagged-box!", 1) };
+    ///
+    /// unsafe {
+    ///     assert_eq!(tagged.as_unsized_ref::<str>(), "Hello from tagged-box!");
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics under the same conditions as [`TaggedBox::new`]
+    ///
+    /// # Safety
+    ///
+    /// `T` must store a `str` for `discriminant`, see [`from_slice`]
+    ///
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
+    /// [`from_slice`]: crate::TaggedBox#from_slice
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub unsafe fn from_str(string: &str, discriminant: Discriminant) -> Self {
+        let value = Layout::for_value(string);
+
+        // Safety: The payload is a copy of `string`, so it's valid UTF-8 of the same length
+        Self::new_unsized(value, discriminant, |payload| {
+            ptr::copy_nonoverlapping(string.as_ptr(), payload, string.len());
+
+            ptr::slice_from_raw_parts_mut(payload, string.len()) as *mut str
+        })
+    }
+
+    /// Creates a new `TaggedBox` by moving the value out of a boxed unsized value, such as a
+    /// `Box<dyn Trait>`, `Box<[U]>` or `Box<str>`
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::{boxed::Box, string::ToString};
+    /// # use core::fmt::Display;
+    /// # use tagged_box::{TaggableInner, TaggedBox};
+    /// #
+    /// # enum Payload {
+    /// #     Numbers(Box<[i32]>),
+    /// #     Text(Box<str>),
+    /// #     Shown(Box<dyn Display>),
+    /// # }
+    /// #
+    /// # impl TaggableInner for Payload {
+    /// #     fn into_tagged_box(self) -> TaggedBox<Self> {
+    /// #         // Safety: Every discriminant is dropped as the unsized type it's created with
+    /// #         unsafe {
+    /// #             match self {
+    /// #                 Self::Numbers(numbers) => TaggedBox::from_box_dyn(numbers, 0),
+    /// #                 Self::Text(text) => TaggedBox::from_box_dyn(text, 1),
+    /// #                 Self::Shown(shown) => TaggedBox::from_box_dyn(shown, 2),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+    /// #         unsafe {
+    /// #             match tagged.discriminant() {
+    /// #                 0 => Self::Numbers(TaggedBox::into_box_unsized(tagged)),
+    /// #                 1 => Self::Text(TaggedBox::into_box_unsized(tagged)),
+    /// #                 2 => Self::Shown(TaggedBox::into_box_unsized(tagged)),
+    /// #                 _ => unreachable!(),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn ref_from_tagged_box<F: FnOnce(&Self)>(tagged: &TaggedBox<Self>, callback: F) {
+    /// #         // Unsized values can't be read out of the box bitwise, so the callback gets a copy
+    /// #         match tagged.discriminant() {
+    /// #             0 => callback(&Self::Numbers(Box::from(tagged.as_unsized_ref::<[i32]>()))),
+    /// #             1 => callback(&Self::Text(Box::from(tagged.as_unsized_ref::<str>()))),
+    /// #             2 => {
+    /// #                 let shown = tagged.as_unsized_ref::<dyn Display>().to_string();
+    /// #                 callback(&Self::Shown(Box::new(shown)))
+    /// #             }
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+    /// #         match tagged.discriminant() {
+    /// #             0 => TaggedBox::drop_inner_unsized::<[i32]>(tagged),
+    /// #             1 => TaggedBox::drop_inner_unsized::<str>(tagged),
+    /// #             2 => TaggedBox::drop_inner_unsized::<dyn Display>(tagged),
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// # }
+    /// let boxed: Box<dyn Display> = Box::new(100_usize);
+    ///
+    /// // Safety: `Payload` stores and drops a `dyn Display` for its third variant
+    /// let tagged = unsafe { TaggedBox::<Payload>::from_box_dyn(boxed, 2) };
+    ///
+    /// unsafe {
+    ///     assert_eq!(tagged.as_unsized_ref::<dyn Display>().to_string(), "100");
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics under the same conditions as [`TaggedBox::new`]
+    ///
+    /// # Safety
+    ///
+    /// `T` must store a `D` for `discriminant`, see [`from_slice`]
+    ///
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
+    /// [`from_slice`]: crate::TaggedBox#from_slice
+    #[inline]
+    pub unsafe fn from_box_dyn<D: ?Sized>(
+        boxed: alloc::boxed::Box<D>,
+        discriminant: Discriminant,
+    ) -> Self {
+        use alloc::boxed::Box;
+
+        let value = Layout::for_value::<D>(&*boxed);
+        let raw = Box::into_raw(boxed);
+
+        // Safety: The payload is moved out of the box and the box's allocation is freed
+        // without dropping it, so the value is never duplicated
+        Self::new_unsized(value, discriminant, |payload| {
+            ptr::copy_nonoverlapping(raw as *const u8, payload, value.size());
+            if value.size() != 0 {
+                alloc::alloc::dealloc(raw as *mut u8, value);
+            }
+
+            provenance::with_data_ptr(raw, payload)
+        })
+    }
+
+    /// Gains a reference to an unsized value stored in the `TaggedBox`
+    ///
+    /// # Safety
+    ///
+    /// The `TaggedBox` must have been created by [`from_slice`], [`from_str`] or [`from_box_dyn`]
+    /// with a value of the type `D`
+    ///
+    /// [`from_slice`]: crate::TaggedBox#from_slice
+    /// [`from_str`]: crate::TaggedBox#from_str
+    /// [`from_box_dyn`]: crate::TaggedBox#from_box_dyn
+    #[inline]
+    pub unsafe fn as_unsized_ref<D: ?Sized>(&self) -> &D {
+        &*self.unsized_ptr::<D>()
+    }
+
+    /// Gains a mutable reference to an unsized value stored in the `TaggedBox`
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`as_unsized_ref`] apply
+    ///
+    /// [`as_unsized_ref`]: crate::TaggedBox#as_unsized_ref
+    #[inline]
+    pub unsafe fn as_unsized_mut<D: ?Sized>(&mut self) -> &mut D {
+        &mut *self.unsized_ptr::<D>()
+    }
+
+    /// Moves an unsized value out of the `TaggedBox` and into a [`Box`]
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::{boxed::Box, string::ToString};
+    /// # use core::fmt::Display;
+    /// # use tagged_box::{TaggableInner, TaggedBox};
+    /// #
+    /// # enum Payload {
+    /// #     Numbers(Box<[i32]>),
+    /// #     Text(Box<str>),
+    /// #     Shown(Box<dyn Display>),
+    /// # }
+    /// #
+    /// # impl TaggableInner for Payload {
+    /// #     fn into_tagged_box(self) -> TaggedBox<Self> {
+    /// #         // Safety: Every discriminant is dropped as the unsized type it's created with
+    /// #         unsafe {
+    /// #             match self {
+    /// #                 Self::Numbers(numbers) => TaggedBox::from_box_dyn(numbers, 0),
+    /// #                 Self::Text(text) => TaggedBox::from_box_dyn(text, 1),
+    /// #                 Self::Shown(shown) => TaggedBox::from_box_dyn(shown, 2),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+    /// #         unsafe {
+    /// #             match tagged.discriminant() {
+    /// #                 0 => Self::Numbers(TaggedBox::into_box_unsized(tagged)),
+    /// #                 1 => Self::Text(TaggedBox::into_box_unsized(tagged)),
+    /// #                 2 => Self::Shown(TaggedBox::into_box_unsized(tagged)),
+    /// #                 _ => unreachable!(),
+    /// #             }
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn ref_from_tagged_box<F: FnOnce(&Self)>(tagged: &TaggedBox<Self>, callback: F) {
+    /// #         // Unsized values can't be read out of the box bitwise, so the callback gets a copy
+    /// #         match tagged.discriminant() {
+    /// #             0 => callback(&Self::Numbers(Box::from(tagged.as_unsized_ref::<[i32]>()))),
+    /// #             1 => callback(&Self::Text(Box::from(tagged.as_unsized_ref::<str>()))),
+    /// #             2 => {
+    /// #                 let shown = tagged.as_unsized_ref::<dyn Display>().to_string();
+    /// #                 callback(&Self::Shown(Box::new(shown)))
+    /// #             }
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// #
+    /// #     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+    /// #         match tagged.discriminant() {
+    /// #             0 => TaggedBox::drop_inner_unsized::<[i32]>(tagged),
+    /// #             1 => TaggedBox::drop_inner_unsized::<str>(tagged),
+    /// #             2 => TaggedBox::drop_inner_unsized::<dyn Display>(tagged),
+    /// #             _ => unreachable!(),
+    /// #         }
+    /// #     }
+    /// # }
+    /// // Safety: `Payload` stores and drops a `str` for its second variant
+    /// let tagged = unsafe { TaggedBox::<Payload>::from_str("Hello from tagged-box!", 1) };
+    ///
+    /// // The value has to be taken out as the type it was stored as
+    /// let boxed: Box<str> = unsafe { TaggedBox::into_box_unsized(tagged) };
+    /// assert_eq!(&*boxed, "Hello from tagged-box!");
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`as_unsized_ref`] apply
+    ///
+    /// [`Box`]: https://doc.rust-lang.org/alloc/boxed/struct.Box.html
+    /// [`as_unsized_ref`]: crate::TaggedBox#as_unsized_ref
+    #[inline]
+    pub unsafe fn into_box_unsized<D: ?Sized>(tagged: Self) -> alloc::boxed::Box<D> {
+        use alloc::boxed::Box;
+
+        let tagged = ManuallyDrop::new(tagged);
+        let payload = tagged.unsized_ptr::<D>();
+        let value = Layout::for_value::<D>(&*payload);
+
+        let boxed = if value.size() == 0 {
+            provenance::without_provenance(value.align()) as *mut u8
+        } else {
+            let boxed = alloc::alloc::alloc(value);
+            if boxed.is_null() {
+                alloc::alloc::handle_alloc_error(value);
+            }
+
+            boxed
+        };
+        ptr::copy_nonoverlapping(payload as *const u8, boxed, value.size());
+
+        // Safety: The value has been moved out of the allocation, so only the
+        // backing memory is left to be freed
+        A::dealloc(
+            tagged.as_ptr::<u8>() as *mut u8,
+            Self::unsized_layout::<D>(value).0,
+        );
+
+        Box::from_raw(provenance::with_data_ptr(payload, boxed))
+    }
+
+    /// Drops an unsized value stored on the heap in place and frees its allocation, used to implement
+    /// [`TaggableInner::drop_tagged_box`]
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`as_unsized_ref`] apply, and `tagged` must not be used in any way
+    /// after this has been called, including being dropped
+    ///
+    /// [`TaggableInner::drop_tagged_box`]: crate::TaggableInner#drop_tagged_box
+    /// [`as_unsized_ref`]: crate::TaggedBox#as_unsized_ref
+    #[inline]
+    pub unsafe fn drop_inner_unsized<D: ?Sized>(tagged: &mut Self) {
+        let payload = tagged.unsized_ptr::<D>();
+        let layout = Self::unsized_layout::<D>(Layout::for_value::<D>(&*payload)).0;
+
+        ptr::drop_in_place(payload);
+        A::dealloc(tagged.as_mut_ptr::<u8>(), layout);
+    }
+
+    /// Allocates a header followed by a payload with the layout of `value`, initializing the payload
+    /// with `init` and storing the fat pointer it returns in the header
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize the payload and return a pointer to it, with the metadata of
+    /// a value matching `value`
+    #[inline]
+    unsafe fn new_unsized<D: ?Sized>(
+        value: Layout,
+        discriminant: Discriminant,
+        init: impl FnOnce(*mut u8) -> *mut D,
+    ) -> Self {
+        let (layout, offset) = Self::unsized_layout::<D>(value);
+
+        // The header is never zero sized, so neither is the allocation
+        let header = A::alloc(layout);
+        if header.is_null() {
+            alloc::alloc::handle_alloc_error(layout);
+        }
+
+        let payload = init(header.add(offset));
+        (header as *mut *mut D).write(payload);
+
+        Self {
+            boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(
+                header as *mut (),
+                discriminant,
+            ),
+            _type: PhantomData,
+        }
+    }
+
+    /// Reads the fat pointer to the payload out of the allocation's header
+    #[inline]
+    unsafe fn unsized_ptr<D: ?Sized>(&self) -> *mut D {
+        self.boxed.as_ptr::<*mut D>().read()
+    }
+
+    /// The layout of a header followed by a payload with the layout of `value`, aligned to at least
+    /// `2 ^ LOW_BITS` bytes, along with the offset of the payload
+    #[inline]
+    fn unsized_layout<D: ?Sized>(value: Layout) -> (Layout, usize) {
+        let (layout, offset) = Layout::new::<*mut D>()
+            .extend(value)
+            .expect("The unsized value is too large to allocate");
+
+        let layout = layout
+            .align_to(TaggedPointer::<FREE_BITS, LOW_BITS>::MIN_ALIGNMENT)
+            .expect("`LOW_BITS` is too large for an allocation alignment");
+
+        (layout, offset)
+    }
+}
+
+/// Immediate payloads, which are stored in the address bits of the pointer instead of being allocated
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > TaggedBox<T, FREE_BITS, LOW_BITS, A>
+{
+    /// The number of bits an [`Immediate`] value stored inline may use. This is every address bit
+    /// of the pointer except for the lowest free one, which is always set so that an inline
+    /// `TaggedBox` is never null
+    ///
+    /// [`Immediate`]: crate::Immediate
+    pub const INLINE_BITS: u32 = TaggedPointer::<FREE_BITS, LOW_BITS>::POINTER_WIDTH - LOW_BITS - 1;
+
+    /// Creates a new `TaggedBox` that stores `val` directly in the pointer instead of allocating it
+    ///
+    /// Inline boxes must only be accessed through [`TaggedBox::as_inline`] and
+    /// [`TaggedBox::into_inline`], and their [`TaggableInner`] impl must not drop or deallocate them
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Number, enum Numbers {
+    ///         #[inline]
+    ///         Short(i16),
+    ///         #[inline]
+    ///         Byte(u8),
+    ///     }
+    /// }
+    ///
+    /// // Safety: `Numbers` stores its first variant inline as an `i16`
+    /// let short: TaggedBox<Numbers> = unsafe { TaggedBox::new_inline(-10i16, 0) };
+    ///
+    /// unsafe {
+    ///     assert_eq!(short.as_inline::<i16>(), -10);
+    /// }
+    /// ```
+    ///
+    /// Values that don't fit into [`INLINE_BITS`] are rejected at compile time
+    ///
+    /// ```compile_fail
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// # tagged_box! {
+    /// #     struct Number, enum Numbers {
+    /// #         #[inline]
+    /// #         Short(i16),
+    /// #     }
+    /// # }
+    /// let shorts: TaggedBox<Numbers> = unsafe { TaggedBox::new_inline([0i16; 4], 0) };
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `discriminant` is greater than [`MAX_DISCRIMINANT`]
+    ///
+    /// # Safety
+    ///
+    /// `T` must store the variant for `discriminant` inline as a `U`, like `#[inline]` variants of
+    /// [`tagged_box!`]. Any other variant is dropped and deallocated by `T`, which would hand the
+    /// bits of `val` to the allocator as if they were a pointer
+    ///
+    /// [`TaggedBox::as_inline`]: crate::TaggedBox#as_inline
+    /// [`TaggedBox::into_inline`]: crate::TaggedBox#into_inline
+    /// [`TaggableInner`]: crate::TaggableInner
+    /// [`tagged_box!`]: crate::tagged_box
+    /// [`INLINE_BITS`]: crate::TaggedBox::INLINE_BITS
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    #[inline]
+    pub unsafe fn new_inline<U: Immediate>(val: U, discriminant: Discriminant) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = InlineCheck::<U, FREE_BITS, LOW_BITS>::FITS;
+
+        // Going through little endian bytes puts the value into the lowest bits on every target
+        let mut bytes = [0; mem::size_of::<usize>()];
+        // `U` fits into a usize and all of its bytes are initialized, see `Immediate`
+        ptr::copy_nonoverlapping(
+            &val as *const U as *const u8,
+            bytes.as_mut_ptr(),
+            mem::size_of::<U>(),
+        );
+        let addr = ((usize::from_le_bytes(bytes) << 1) | 1) << LOW_BITS;
+
+        Self {
+            boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(
+                provenance::without_provenance(addr),
+                discriminant,
+            ),
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns a copy of the value stored inline in the `TaggedBox`
+    ///
+    /// # Safety
+    ///
+    /// The `TaggedBox` must have been created by [`TaggedBox::new_inline`] with a `U`
+    ///
+    /// [`TaggedBox::new_inline`]: crate::TaggedBox#new_inline
+    #[inline]
+    pub unsafe fn as_inline<U: Immediate>(&self) -> U {
+        let bits = (self.boxed.as_u64() as usize >> LOW_BITS) >> 1;
+        let bytes = bits.to_le_bytes();
+
+        let mut val = MaybeUninit::<U>::uninit();
+        ptr::copy_nonoverlapping(
+            bytes.as_ptr(),
+            val.as_mut_ptr() as *mut u8,
+            mem::size_of::<U>(),
+        );
+
+        val.assume_init()
+    }
+
+    /// Returns the value stored inline in the `TaggedBox`, consuming it without running
+    /// [`TaggableInner::drop_tagged_box`]
+    ///
+    /// # Safety
+    ///
+    /// The `TaggedBox` must have been created by [`TaggedBox::new_inline`] with a `U`
+    ///
+    /// [`TaggableInner::drop_tagged_box`]: crate::TaggableInner#drop_tagged_box
+    /// [`TaggedBox::new_inline`]: crate::TaggedBox#new_inline
+    #[inline]
+    pub unsafe fn into_inline<U: Immediate>(tagged: Self) -> U {
+        ManuallyDrop::new(tagged).as_inline()
+    }
+}
+
+/// Checked downcasts, which look up the stored type in the [`TaggableInner::variant_type`] type table
+///
+/// The table describes what `T` stores for each discriminant rather than what was actually stored,
+/// which is only the same because every constructor that takes a discriminant along with a value
+/// is unsafe. A value of the wrong type can't be stored from safe code
+///
+/// ```compile_fail,E0133
+/// # use tagged_box::{tagged_box, TaggedBox};
+/// tagged_box! {
+///     struct Brick, enum Bricks {
+///         Small(u8),
+///         Large([u64; 8]),
+///     }
+/// }
+///
+/// // `Large` holds a `[u64; 8]`, so downcasting this `u8` to one would read past its allocation
+/// let large_brick: TaggedBox<Bricks> = TaggedBox::new(1_u8, 1);
+/// ```
+///
+/// [`TaggableInner::variant_type`]: crate::TaggableInner#variant_type
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > TaggedBox<T, FREE_BITS, LOW_BITS, A>
+{
+    /// Returns the type stored behind the pointer, or `None` if `T` doesn't know it
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox, VariantType};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
+    /// }
+    ///
+    /// let red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
+    /// assert_eq!(red_brick.variant_type(), Some(VariantType::of::<usize>()));
+    /// ```
+    #[inline]
+    pub fn variant_type(&self) -> Option<VariantType> {
+        T::variant_type(self.discriminant())
+    }
+
+    /// Returns true if the value stored behind the pointer is a `U`
+    #[inline]
+    pub fn is<U: 'static>(&self) -> bool {
+        matches!(self.variant_type(), Some(variant) if variant.is::<U>())
+    }
+
+    /// Returns a reference to the value stored on the heap if it's a `U`, the checked version of
+    /// [`TaggedBox::as_ref`]
+    ///
+    /// ```rust
+    /// # extern crate alloc;
+    /// # use alloc::string::String;
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///         Named(String),
+    ///     }
+    /// }
+    ///
+    /// let red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
+    ///
+    /// assert_eq!(red_brick.downcast_ref::<usize>(), Some(&100));
+    /// assert_eq!(red_brick.downcast_ref::<String>(), None);
+    /// ```
+    ///
+    /// [`TaggedBox::as_ref`]: crate::TaggedBox#as_ref
+    #[inline]
+    pub fn downcast_ref<U: 'static>(&self) -> Option<&U> {
+        if self.is::<U>() {
+            // Safety: The type table says that a `U` is stored for this discriminant
+            Some(unsafe { self.as_ref::<U>() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value stored on the heap if it's a `U`, the checked
+    /// version of [`TaggedBox::as_mut_ref`]
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///     }
+    /// }
+    ///
+    /// let mut red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
+    ///
+    /// if let Some(bricks) = red_brick.downcast_mut::<usize>() {
+    ///     *bricks = 300;
+    /// }
+    /// assert_eq!(red_brick.downcast_ref::<usize>(), Some(&300));
+    /// ```
+    ///
+    /// [`TaggedBox::as_mut_ref`]: crate::TaggedBox#as_mut_ref
+    #[inline]
+    pub fn downcast_mut<U: 'static>(&mut self) -> Option<&mut U> {
+        if self.is::<U>() {
+            // Safety: The type table says that a `U` is stored for this discriminant
+            Some(unsafe { self.as_mut_ref::<U>() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value stored on the heap if it's a `U` and gives back the `TaggedBox` otherwise,
+    /// the checked version of [`TaggedBox::into_inner`]
+    ///
+    /// ```rust
+    /// # use tagged_box::{tagged_box, TaggedBox};
+    /// tagged_box! {
+    ///     struct Brick, enum Bricks {
+    ///         Red(usize),
+    ///         Blue(u8),
+    ///     }
+    /// }
+    ///
+    /// let red_brick: TaggedBox<Bricks> = unsafe { TaggedBox::new(100_usize, 0) };
+    ///
+    /// let red_brick = TaggedBox::downcast::<u8>(red_brick).unwrap_err();
+    /// assert_eq!(TaggedBox::downcast::<usize>(red_brick).ok(), Some(100));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `tagged` if the value stored on the heap isn't a `U`
+    ///
+    /// [`TaggedBox::into_inner`]: crate::TaggedBox#into_inner
+    #[inline]
+    pub fn downcast<U: 'static>(tagged: Self) -> Result<U, Self> {
+        if tagged.is::<U>() {
+            // Safety: The type table says that a `U` is stored for this discriminant
+            Ok(unsafe { Self::into_inner::<U>(tagged) })
+        } else {
+            Err(tagged)
+        }
+    }
+}
+
+/// In-place mutation, used to implement [`TaggableInner::mut_from_tagged_box`]
+///
+/// [`TaggableInner::mut_from_tagged_box`]: crate::TaggableInner#mut_from_tagged_box
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > TaggedBox<T, FREE_BITS, LOW_BITS, A>
+{
+    /// Moves the value out of `tagged` with [`TaggableInner::from_tagged_box`], runs `callback` on
+    /// it and allocates it again with [`TaggableInner::into_tagged_box`]. The value is stored back
+    /// even if `callback` panics
+    ///
+    /// [`TaggableInner::from_tagged_box`]: crate::TaggableInner#from_tagged_box
+    /// [`TaggableInner::into_tagged_box`]: crate::TaggableInner#into_tagged_box
+    pub(crate) fn mutate_reboxed<C>(tagged: &mut Self, callback: C)
+    where
+        C: FnOnce(&mut T),
+    {
+        // Safety: `tagged` is overwritten by the guard before it's used again
+        let value = T::from_tagged_box(unsafe { ptr::read(tagged) });
+
+        Rebox::new(tagged, value, Recycled::empty(), |value, _| {
+            value.into_tagged_box()
+        })
+        .run(callback);
+    }
+
+    /// Moves the value out of `tagged` while keeping its allocation, runs `callback` on it and
+    /// stores it back with `rebox`, which can reuse the old allocation through
+    /// [`TaggedBox::new_recycled`]. Whatever isn't reused is freed afterwards, and the value is
+    /// stored back even if `callback` panics
+    ///
+    /// # Safety
+    ///
+    /// `T::ref_from_tagged_box` must hand out a bitwise copy of the value and `T::variant_type`
+    /// must give the type allocated for every discriminant, like the ones generated by
+    /// [`tagged_box!`]
+    ///
+    /// [`TaggedBox::new_recycled`]: crate::TaggedBox#new_recycled
+    /// [`tagged_box!`]: macro.tagged_box.html
+    #[doc(hidden)]
+    pub unsafe fn mutate_recycled<C, R>(tagged: &mut Self, callback: C, rebox: R)
+    where
+        C: FnOnce(&mut T),
+        R: FnOnce(T, &mut Recycled<A>) -> Self,
+    {
+        let mut value = None;
+        T::ref_from_tagged_box(tagged, |borrowed| value = Some(ptr::read(borrowed)));
+        let value = value.expect("`ref_from_tagged_box` didn't run its callback");
+
+        let recycled = match T::variant_type(tagged.discriminant()) {
+            Some(variant) if variant.layout().size() != 0 => Recycled::new(
+                tagged.as_mut_ptr::<u8>(),
+                Self::aligned_layout(variant.layout()),
+            ),
+            _ => Recycled::empty(),
+        };
+
+        Rebox::new(tagged, value, recycled, rebox).run(callback);
+    }
+
+    /// Creates a new `TaggedBox` like [`TaggedBox::new`], but writes `val` into the recycled
+    /// allocation instead of allocating when it has the layout a `U` needs
+    ///
+    /// # Safety
+    ///
+    /// `U` must be the type `T` stores for `discriminant`, see [`TaggedBox::new`]
+    ///
+    /// [`TaggedBox::new`]: crate::TaggedBox#new
+    #[doc(hidden)]
+    #[inline]
+    pub unsafe fn new_recycled<U>(
+        val: U,
+        discriminant: Discriminant,
+        recycled: &mut Recycled<A>,
+    ) -> Self {
+        if mem::size_of::<U>() != 0 {
+            if let Some(ptr) = recycled.take(Self::layout::<U>()) {
+                let ptr = ptr as *mut U;
+
+                // The allocation was made for the same layout and nothing else owns it
+                ptr.write(val);
+
+                return Self {
+                    boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::from_mut_ptr(
+                        ptr as *mut (),
+                        discriminant,
+                    ),
+                    _type: PhantomData,
+                };
+            }
+        }
+
+        Self::new(val, discriminant)
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Drop for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+{
+    fn drop(&mut self) {
+        // Safety: The box is never touched again after its inner value is dropped
+        unsafe { T::drop_tagged_box(self) }
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Debug
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + fmt::Debug + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        unsafe {
+            T::ref_from_tagged_box(self, |this| {
+                result = write!(f, "{:?}", this);
+            });
+        }
+
+        result
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Display
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + fmt::Display + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        unsafe {
+            T::ref_from_tagged_box(self, |this| {
+                result = write!(f, "{}", this);
+            });
+        }
+
+        result
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Clone
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Clone,
+{
+    fn clone(&self) -> Self {
+        T::clone_tagged_box(self)
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> PartialEq
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + PartialEq<T>,
+{
+    fn eq(&self, other: &TaggedBox<T, FREE_BITS, LOW_BITS, A>) -> bool {
+        let mut eq = false;
         unsafe {
             T::ref_from_tagged_box(self, |this| {
                 T::ref_from_tagged_box(other, |other| {
@@ -550,13 +1990,19 @@ where
     }
 }
 
-impl<T> Eq for TaggedBox<T> where T: TaggableInner + Eq {}
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Eq
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Eq,
+{
+}
 
-impl<T> PartialOrd for TaggedBox<T>
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> PartialOrd
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
 where
-    T: TaggableInner + PartialOrd<T>,
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + PartialOrd<T>,
 {
-    fn partial_cmp(&self, other: &TaggedBox<T>) -> Option<cmp::Ordering> {
+    fn partial_cmp(&self, other: &TaggedBox<T, FREE_BITS, LOW_BITS, A>) -> Option<cmp::Ordering> {
         let mut cmp = None;
         unsafe {
             T::ref_from_tagged_box(self, |this| {
@@ -566,40 +2012,185 @@ where
             });
         }
 
-        cmp
+        cmp
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Ord
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Ord,
+{
+    fn cmp(&self, other: &TaggedBox<T, FREE_BITS, LOW_BITS, A>) -> cmp::Ordering {
+        let mut cmp = cmp::Ordering::Equal;
+        unsafe {
+            T::ref_from_tagged_box(self, |this| {
+                T::ref_from_tagged_box(other, |other| {
+                    cmp = this.cmp(other);
+                });
+            });
+        }
+
+        cmp
+    }
+}
+
+// Hashes the stored value as its enum, so that boxes that are equal hash the same
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Hash
+    for TaggedBox<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe {
+            T::ref_from_tagged_box(self, |this| this.hash(state));
+        }
+    }
+}
+
+impl_fmt!(impl[T: TaggableInner<FREE_BITS, LOW_BITS, A>, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator] TaggedBox<T, FREE_BITS, LOW_BITS, A> => LowerHex, UpperHex, Binary, Octal);
+
+/// An allocation left behind by a value moved out of a [`TaggedBox`], which can be reused by the
+/// next value stored in it. Whatever isn't reused is freed when it's dropped
+///
+/// [`TaggedBox`]: crate::TaggedBox
+#[doc(hidden)]
+pub struct Recycled<A: RawAllocator> {
+    allocation: Option<(*mut u8, Layout)>,
+    _allocator: PhantomData<A>,
+}
+
+impl<A: RawAllocator> Recycled<A> {
+    fn new(ptr: *mut u8, layout: Layout) -> Self {
+        Self {
+            allocation: Some((ptr, layout)),
+            _allocator: PhantomData,
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            allocation: None,
+            _allocator: PhantomData,
+        }
+    }
+
+    /// Takes the allocation if it was made with exactly `layout`, as it has to be freed with the
+    /// same one
+    fn take(&mut self, layout: Layout) -> Option<*mut u8> {
+        match self.allocation {
+            Some((ptr, recycled)) if recycled == layout => {
+                self.allocation = None;
+                Some(ptr)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<A: RawAllocator> Drop for Recycled<A> {
+    fn drop(&mut self) {
+        if let Some((ptr, layout)) = self.allocation.take() {
+            // Safety: The value was moved out of the allocation and nothing else owns it
+            unsafe { A::dealloc(ptr, layout) }
+        }
+    }
+}
+
+/// Stores a value back into its `TaggedBox` once it's done being mutated, even when unwinding
+struct Rebox<'a, T, R, const FREE_BITS: u32, const LOW_BITS: u32, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    R: FnOnce(T, &mut Recycled<A>) -> TaggedBox<T, FREE_BITS, LOW_BITS, A>,
+    A: RawAllocator,
+{
+    tagged: &'a mut TaggedBox<T, FREE_BITS, LOW_BITS, A>,
+    value: ManuallyDrop<T>,
+    rebox: ManuallyDrop<R>,
+    recycled: Recycled<A>,
+}
+
+impl<'a, T, R, const FREE_BITS: u32, const LOW_BITS: u32, A> Rebox<'a, T, R, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    R: FnOnce(T, &mut Recycled<A>) -> TaggedBox<T, FREE_BITS, LOW_BITS, A>,
+    A: RawAllocator,
+{
+    /// `tagged` must have been moved out of, it's overwritten without being dropped
+    fn new(
+        tagged: &'a mut TaggedBox<T, FREE_BITS, LOW_BITS, A>,
+        value: T,
+        recycled: Recycled<A>,
+        rebox: R,
+    ) -> Self {
+        Self {
+            tagged,
+            value: ManuallyDrop::new(value),
+            rebox: ManuallyDrop::new(rebox),
+            recycled,
+        }
+    }
+
+    fn run<C: FnOnce(&mut T)>(mut self, callback: C) {
+        callback(&mut self.value);
     }
 }
 
-impl<T> Ord for TaggedBox<T>
+impl<'a, T, R, const FREE_BITS: u32, const LOW_BITS: u32, A> Drop
+    for Rebox<'a, T, R, FREE_BITS, LOW_BITS, A>
 where
-    T: TaggableInner + Ord,
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    R: FnOnce(T, &mut Recycled<A>) -> TaggedBox<T, FREE_BITS, LOW_BITS, A>,
+    A: RawAllocator,
 {
-    fn cmp(&self, other: &TaggedBox<T>) -> cmp::Ordering {
-        let mut cmp = cmp::Ordering::Equal;
+    fn drop(&mut self) {
+        // Until it's overwritten, `tagged` still points to the allocation the value was moved out
+        // of, which `recycled` frees. Unwinding out of `rebox` would leave it dangling for its
+        // owner to drop again, so that aborts instead
+        let abort = AbortOnUnwind;
+
+        // Safety: The value and closure are only taken here, and the box they're written into
+        // was already moved out of
         unsafe {
-            T::ref_from_tagged_box(self, |this| {
-                T::ref_from_tagged_box(other, |other| {
-                    cmp = this.cmp(other);
-                });
-            });
+            let value = ManuallyDrop::take(&mut self.value);
+            let rebox = ManuallyDrop::take(&mut self.rebox);
+            ptr::write(self.tagged, rebox(value, &mut self.recycled));
         }
 
-        cmp
+        mem::forget(abort);
+    }
+}
+
+/// Aborts the process if it's dropped, which only happens while unwinding as it's forgotten
+/// otherwise. `core` has no way to abort, but panicking while already unwinding does
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!("Panicked while storing a value back into its `TaggedBox`, aborting");
     }
 }
 
-impl_fmt!(impl[T: TaggableInner] TaggedBox<T> => LowerHex, UpperHex, Binary, Octal);
+/// Checks at compile time that a `U` fits into the inline bits of a `TaggedBox`
+struct InlineCheck<U, const FREE_BITS: u32, const LOW_BITS: u32>(PhantomData<U>);
+
+impl<U, const FREE_BITS: u32, const LOW_BITS: u32> InlineCheck<U, FREE_BITS, LOW_BITS> {
+    const FITS: () = assert!(
+        mem::size_of::<U>() * 8
+            < (TaggedPointer::<FREE_BITS, LOW_BITS>::POINTER_WIDTH - LOW_BITS) as usize,
+        "The immediate value is too large to be stored inline",
+    );
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{tagged_box, TaggableContainer, TaggableInner};
-    use alloc::{string::String, vec, vec::Vec};
+    use alloc::{boxed::Box, rc::Rc, string::String, vec, vec::Vec};
     use core::mem::ManuallyDrop;
 
     #[test]
     fn new() {
-        enum Test {}
         #[derive(Debug, PartialEq)]
         struct Custom {
             a: u32,
@@ -607,18 +2198,29 @@ mod tests {
             c: [usize; 10],
         }
 
-        let integer: TaggedBox<Test> =
-            TaggedBox::new(0xF00D_BEEF_usize, crate::discriminant::MAX_DISCRIMINANT / 2);
-        let custom: TaggedBox<Test> = TaggedBox::new(
-            Custom {
-                a: 10000,
-                b: false,
-                c: [(usize::max_value() << 32) >> 32; 10],
-            },
-            crate::discriminant::MAX_DISCRIMINANT / 4,
-        );
-        let string: TaggedBox<Test> = TaggedBox::new(String::from("Hello world!"), 0);
-        let vec: TaggedBox<Test> = TaggedBox::new(vec![1i32, 2, 3, 4, 5, 6], 10);
+        tagged_box! {
+            #[allow(dead_code)]
+            struct Container, enum Test {
+                Usize(usize),
+                Custom(Custom),
+                String(String),
+                Vec(Vec<i32>),
+            }
+        }
+
+        let integer: TaggedBox<Test> = unsafe { TaggedBox::new(0xF00D_BEEF_usize, 0) };
+        let custom: TaggedBox<Test> = unsafe {
+            TaggedBox::new(
+                Custom {
+                    a: 10000,
+                    b: false,
+                    c: [(usize::MAX << 32) >> 32; 10],
+                },
+                1,
+            )
+        };
+        let string: TaggedBox<Test> = unsafe { TaggedBox::new(String::from("Hello world!"), 2) };
+        let vec: TaggedBox<Test> = unsafe { TaggedBox::new(vec![1i32, 2, 3, 4, 5, 6], 3) };
 
         unsafe {
             assert_eq!(0xF00D_BEEF_usize, TaggedBox::into_inner(integer));
@@ -626,7 +2228,7 @@ mod tests {
                 Custom {
                     a: 10000,
                     b: false,
-                    c: [(usize::max_value() << 32) >> 32; 10],
+                    c: [(usize::MAX << 32) >> 32; 10],
                 },
                 TaggedBox::into_inner(custom)
             );
@@ -643,12 +2245,19 @@ mod tests {
 
     #[test]
     fn new_zst() {
-        enum Test {}
         #[derive(Debug, PartialEq)]
         struct Zst;
 
-        let unit: TaggedBox<Test> = TaggedBox::new((), 0);
-        let zst: TaggedBox<Test> = TaggedBox::new(Zst, crate::discriminant::MAX_DISCRIMINANT);
+        tagged_box! {
+            #[allow(dead_code)]
+            struct Container, enum Test {
+                Unit(()),
+                Zst(Zst),
+            }
+        }
+
+        let unit: TaggedBox<Test> = unsafe { TaggedBox::new((), 0) };
+        let zst: TaggedBox<Test> = unsafe { TaggedBox::new(Zst, 1) };
 
         unsafe {
             assert_eq!((), TaggedBox::into_inner(unit));
@@ -656,6 +2265,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn drop_payload() {
+        tagged_box! {
+            struct Container, enum Item {
+                Counted(Rc<()>),
+                Pair(Rc<()>, Rc<()>),
+                Orphan {
+                    counted: Rc<()>,
+                },
+                Unit,
+            }
+        }
+
+        let counter = Rc::new(());
+
+        let single = Container::from(counter.clone());
+        let pair = Container::from((counter.clone(), counter.clone()));
+        let orphan = Container::from(Item::Orphan {
+            counted: counter.clone(),
+        });
+        let unit = Container::from(Item::Unit);
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        drop(single);
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(pair);
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        drop(orphan);
+        drop(unit);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let boxed: TaggedBox<Item> = unsafe { TaggedBox::new(counter.clone(), 0) };
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        let inner = unsafe { TaggedBox::into_inner::<Rc<()>>(boxed) };
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        drop(inner);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
     #[test]
     fn clone() {
         tagged_box! {
@@ -694,6 +2346,83 @@ mod tests {
         assert_ne!(cloned_string, cloned_usize);
     }
 
+    #[test]
+    fn clone_variants() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        // Records where it was cloned from, which is its allocation when it's cloned in place
+        static CLONED_FROM: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, PartialEq)]
+        struct Located(u32);
+
+        impl Clone for Located {
+            fn clone(&self) -> Self {
+                CLONED_FROM.store(self as *const Self as usize, Ordering::SeqCst);
+                Self(self.0)
+            }
+        }
+
+        tagged_box! {
+            #[tagged_box(low_bits = 2)]
+            #[derive(Debug, Clone, PartialEq)]
+            struct Container, enum Item {
+                Single(Located),
+                Pair(Located, String),
+                #[inline]
+                Short(i16),
+                Fields {
+                    located: Located,
+                    text: String,
+                },
+                Nothing,
+            }
+        }
+
+        let single = Item::Single(Located(1)).into_tagged_box();
+        let cloned = single.clone();
+        assert_eq!(
+            CLONED_FROM.load(Ordering::SeqCst),
+            single.as_ptr::<Located>() as usize
+        );
+        assert_ne!(cloned.as_ptr::<Located>(), single.as_ptr::<Located>());
+        assert_eq!(cloned.discriminant(), single.discriminant());
+        assert_eq!(Item::from_tagged_box(cloned), Item::Single(Located(1)));
+
+        // Multi-field variants start with their first field, as they're `repr(C)`
+        let pair = Item::Pair(Located(2), String::from("Pair")).into_tagged_box();
+        let cloned = pair.clone();
+        assert_eq!(
+            CLONED_FROM.load(Ordering::SeqCst),
+            pair.as_ptr::<Located>() as usize
+        );
+        assert_eq!(
+            Item::from_tagged_box(cloned),
+            Item::Pair(Located(2), String::from("Pair"))
+        );
+
+        let fields = Item::Fields {
+            located: Located(3),
+            text: String::from("Fields"),
+        }
+        .into_tagged_box();
+        let cloned = fields.clone();
+        assert_eq!(
+            CLONED_FROM.load(Ordering::SeqCst),
+            fields.as_ptr::<Located>() as usize
+        );
+        assert_eq!(cloned, fields);
+
+        let short = Item::Short(-3).into_tagged_box();
+        assert_eq!(Item::from_tagged_box(short.clone()), Item::Short(-3));
+
+        let nothing = Item::Nothing.into_tagged_box();
+        assert_eq!(Item::from_tagged_box(nothing.clone()), Item::Nothing);
+
+        let container = Container::from(Item::Single(Located(4)));
+        assert_eq!(container.clone(), container);
+    }
+
     #[test]
     fn tagged_box() {
         #[derive(Clone)]
@@ -724,8 +2453,8 @@ mod tests {
         impl TaggableInner for Value {
             fn into_tagged_box(self) -> TaggedBox<Self> {
                 match self {
-                    Self::I32(int) => TaggedBox::new(int, 0),
-                    Self::Bool(boolean) => TaggedBox::new(boolean, 1),
+                    Self::I32(int) => unsafe { TaggedBox::new(int, 0) },
+                    Self::Bool(boolean) => unsafe { TaggedBox::new(boolean, 1) },
                 }
             }
 
@@ -751,13 +2480,21 @@ mod tests {
 
                 (callback)(&value);
             }
+
+            unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+                match tagged.discriminant() {
+                    0 => TaggedBox::drop_inner::<i32>(tagged),
+                    1 => TaggedBox::drop_inner::<bool>(tagged),
+                    _ => unreachable!(),
+                }
+            }
         }
 
         let int_container = Container {
-            tagged: TaggedBox::new::<i32>(110, 0),
+            tagged: unsafe { TaggedBox::new::<i32>(110, 0) },
         };
         let bool_container = Container {
-            tagged: TaggedBox::new::<bool>(true, 1),
+            tagged: unsafe { TaggedBox::new::<bool>(true, 1) },
         };
 
         assert_eq!(int_container.tagged.discriminant(), 0);
@@ -766,6 +2503,16 @@ mod tests {
         assert_eq!(unsafe { int_container.tagged.as_ref::<i32>() }, &110i32);
         assert_eq!(unsafe { bool_container.tagged.as_ref::<bool>() }, &true);
 
+        // There's no type table, so nothing can be downcast
+        assert_eq!(int_container.tagged.variant_type(), None);
+        assert_eq!(int_container.tagged.downcast_ref::<i32>(), None);
+
+        // Without a type table, mutating moves the value out and allocates it again
+        let mut reboxed = unsafe { TaggedBox::<Value>::new::<i32>(1, 0) };
+        Value::mut_from_tagged_box(&mut reboxed, |value| *value = Value::Bool(true));
+        assert_eq!(reboxed.discriminant(), 1);
+        assert_eq!(Value::from_tagged_box(reboxed), Value::Bool(true));
+
         assert_eq!(int_container.clone().into_inner(), Value::I32(110));
         assert_eq!(bool_container.clone().into_inner(), Value::Bool(true));
 
@@ -824,4 +2571,430 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn low_bits() {
+        // Implemented by hand to give the second variant the highest discriminant that fits into
+        // the lower bits, while `tagged_box!` numbers its variants in order
+        #[derive(Debug, PartialEq)]
+        enum Low {
+            Byte(u8),
+            String(String),
+        }
+
+        impl TaggableInner<0, 4> for Low {
+            fn into_tagged_box(self) -> TaggedBox<Self, 0, 4> {
+                match self {
+                    Self::Byte(byte) => unsafe { TaggedBox::new(byte, 0) },
+                    Self::String(string) => unsafe { TaggedBox::new(string, 15) },
+                }
+            }
+
+            fn from_tagged_box(tagged: TaggedBox<Self, 0, 4>) -> Self {
+                unsafe {
+                    match tagged.discriminant() {
+                        0 => Self::Byte(TaggedBox::into_inner(tagged)),
+                        15 => Self::String(TaggedBox::into_inner(tagged)),
+                        _ => unreachable!(),
+                    }
+                }
+            }
+
+            unsafe fn ref_from_tagged_box<F>(tagged: &TaggedBox<Self, 0, 4>, callback: F)
+            where
+                F: FnOnce(&Self),
+            {
+                let value = ManuallyDrop::new(match tagged.discriminant() {
+                    0 => Self::Byte(tagged.as_ptr::<u8>().read()),
+                    15 => Self::String(tagged.as_ptr::<String>().read()),
+                    _ => unreachable!(),
+                });
+
+                (callback)(&value);
+            }
+
+            unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self, 0, 4>) {
+                match tagged.discriminant() {
+                    0 => TaggedBox::drop_inner::<u8>(tagged),
+                    15 => TaggedBox::drop_inner::<String>(tagged),
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        // `u8`s are only aligned to 1 byte, so the allocation has to be over-aligned
+        let byte = Low::Byte(u8::MAX).into_tagged_box();
+        assert_eq!(byte.discriminant(), 0);
+        assert_eq!(byte.as_ptr::<u8>() as usize % 16, 0);
+
+        let string = Low::String(String::from("Hello world!")).into_tagged_box();
+        assert_eq!(string.discriminant(), 15);
+        assert_eq!(string.as_ptr::<String>() as usize % 16, 0);
+
+        assert_eq!(Low::from_tagged_box(byte), Low::Byte(u8::MAX));
+        assert_eq!(
+            Low::from_tagged_box(string),
+            Low::String(String::from("Hello world!"))
+        );
+
+        let boxed = unsafe { TaggedBox::into_box::<u8>(Low::Byte(10).into_tagged_box()) };
+        assert_eq!(*boxed, 10);
+
+        drop(Low::String(String::from("Dropped")).into_tagged_box());
+
+        // The box's allocation isn't aligned enough to be reused, so it's moved into a new one
+        let byte = unsafe { TaggedBox::<Low, 0, 4>::from_box(Box::new(20u8), 0) };
+        assert_eq!(byte.as_ptr::<u8>() as usize % 16, 0);
+        assert_eq!(Low::from_tagged_box(byte), Low::Byte(20));
+    }
+
+    #[test]
+    fn box_round_trip() {
+        tagged_box! {
+            #[allow(dead_code)]
+            struct Container, enum Test {
+                Counted(Rc<()>),
+                Unit(()),
+            }
+        }
+
+        let counter = Rc::new(());
+
+        // The allocation of the box is reused, so the value isn't moved
+        let boxed = Box::new(counter.clone());
+        let ptr = &*boxed as *const Rc<()>;
+        let tagged = unsafe { TaggedBox::<Test>::from_box(boxed, 0) };
+        assert_eq!(tagged.discriminant(), 0);
+        assert_eq!(tagged.as_ptr::<Rc<()>>(), ptr);
+
+        let boxed = unsafe { TaggedBox::into_box::<Rc<()>>(tagged) };
+        assert_eq!(&*boxed as *const Rc<()>, ptr);
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        let tagged = unsafe { TaggedBox::<Test>::from_box(boxed, 0) };
+        drop(tagged);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let unit = unsafe { TaggedBox::<Test>::from_box(Box::new(()), 1) };
+        assert_eq!(unit.discriminant(), 1);
+        unsafe {
+            assert_eq!(*TaggedBox::into_box::<()>(unit), ());
+        }
+
+        // Leaked values can be reclaimed through `from_raw`
+        let leaked: &'static mut Rc<()> =
+            unsafe { TaggedBox::leak(TaggedBox::<Test>::new(counter.clone(), 0)) };
+        assert!(Rc::ptr_eq(leaked, &counter));
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        let reclaimed = unsafe { TaggedBox::<Test>::from_raw(leaked as *mut Rc<()>, 0) };
+        drop(reclaimed);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn try_new() {
+        tagged_box! {
+            #[allow(dead_code)]
+            struct Container, enum Test {
+                Counted(Rc<()>),
+                Unit(()),
+            }
+        }
+
+        let counter = Rc::new(());
+        let max = <TaggedPointer>::MAX_DISCRIMINANT;
+
+        let counted = unsafe { TaggedBox::<Test>::try_new(counter.clone(), 0) }.unwrap();
+        assert_eq!(counted.discriminant(), 0);
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        // The value is dropped if it can't be tagged
+        let err = unsafe { TaggedBox::<Test>::try_new(counter.clone(), max + 1) }.map(|_| ());
+        assert_eq!(
+            err,
+            Err(TagError::DiscriminantOutOfRange {
+                discriminant: max + 1,
+                max,
+            }),
+        );
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        let unit = unsafe { TaggedBox::<Test>::try_new((), 1) }.unwrap();
+        assert_eq!(unit.discriminant(), 1);
+
+        let raw: *mut Rc<()> = TaggedBox::into_raw(counted);
+        let err = unsafe { TaggedBox::<Test>::try_from_raw(raw, max + 1) }.map(|_| ());
+        assert!(err.is_err());
+
+        let counted = unsafe { TaggedBox::<Test>::try_from_raw(raw, 0) }.unwrap();
+        drop(counted);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn set_discriminant() {
+        #[repr(transparent)]
+        struct Wrapper(Rc<()>);
+
+        tagged_box! {
+            #[allow(dead_code)]
+            struct Container, enum Test {
+                Counted(Rc<()>),
+                Wrapped(Wrapper),
+            }
+        }
+
+        let counter = Rc::new(());
+        let mut tagged = unsafe { TaggedBox::<Test>::new(counter.clone(), 0) };
+        let ptr = tagged.as_ptr::<Rc<()>>();
+
+        // `Wrapper` is a transparent wrapper of `Rc<()>`, so the value can be reinterpreted in place
+        unsafe { tagged.set_discriminant(1) };
+        assert_eq!(tagged.discriminant(), 1);
+        assert_eq!(tagged.as_ptr::<Wrapper>() as *const Rc<()>, ptr);
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        let wrapper = unsafe { TaggedBox::into_inner::<Wrapper>(tagged) };
+        assert!(Rc::ptr_eq(&wrapper.0, &counter));
+
+        drop(wrapper);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn unsized_payloads() {
+        use alloc::format;
+        use core::fmt::Debug;
+
+        // Implemented by hand, as `tagged_box!` only stores sized values
+        #[derive(Debug)]
+        enum Payload {
+            Text(Box<str>),
+            Counted(Box<[Rc<()>]>),
+            Shown(Box<dyn Debug>),
+        }
+
+        impl TaggableInner for Payload {
+            fn into_tagged_box(self) -> TaggedBox<Self> {
+                // Safety: Every discriminant is dropped as the unsized type it's created with
+                unsafe {
+                    match self {
+                        Self::Text(text) => TaggedBox::from_box_dyn(text, 0),
+                        Self::Counted(counted) => TaggedBox::from_box_dyn(counted, 1),
+                        Self::Shown(shown) => TaggedBox::from_box_dyn(shown, 2),
+                    }
+                }
+            }
+
+            fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+                unsafe {
+                    match tagged.discriminant() {
+                        0 => Self::Text(TaggedBox::into_box_unsized(tagged)),
+                        1 => Self::Counted(TaggedBox::into_box_unsized(tagged)),
+                        2 => Self::Shown(TaggedBox::into_box_unsized(tagged)),
+                        _ => unreachable!(),
+                    }
+                }
+            }
+
+            unsafe fn ref_from_tagged_box<F>(tagged: &TaggedBox<Self>, callback: F)
+            where
+                F: FnOnce(&Self),
+            {
+                match tagged.discriminant() {
+                    0 => (callback)(&Self::Text(Box::from(tagged.as_unsized_ref::<str>()))),
+                    1 => (callback)(&Self::Counted(Box::from(
+                        tagged.as_unsized_ref::<[Rc<()>]>(),
+                    ))),
+                    2 => (callback)(&Self::Shown(Box::new(format!(
+                        "{:?}",
+                        tagged.as_unsized_ref::<dyn Debug>(),
+                    )))),
+                    _ => unreachable!(),
+                }
+            }
+
+            unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+                match tagged.discriminant() {
+                    0 => TaggedBox::drop_inner_unsized::<str>(tagged),
+                    1 => TaggedBox::drop_inner_unsized::<[Rc<()>]>(tagged),
+                    2 => TaggedBox::drop_inner_unsized::<dyn Debug>(tagged),
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        assert_eq!(
+            mem::size_of::<TaggedBox<Payload>>(),
+            mem::size_of::<usize>()
+        );
+
+        let text = unsafe { TaggedBox::<Payload>::from_str("Hello world!", 0) };
+        unsafe {
+            assert_eq!(text.as_unsized_ref::<str>(), "Hello world!");
+        }
+        match Payload::from_tagged_box(text) {
+            Payload::Text(text) => assert_eq!(&*text, "Hello world!"),
+            _ => unreachable!(),
+        }
+
+        let empty = unsafe { TaggedBox::<Payload>::from_str("", 0) };
+        unsafe {
+            assert_eq!(empty.as_unsized_ref::<str>(), "");
+        }
+        drop(empty);
+
+        let counter = Rc::new(());
+        let mut counted =
+            unsafe { TaggedBox::<Payload>::from_slice(&[counter.clone(), counter.clone()], 1) };
+        assert_eq!(Rc::strong_count(&counter), 3);
+        unsafe {
+            counted.as_unsized_mut::<[Rc<()>]>()[0] = Rc::new(());
+        }
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(counted);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let shown = Payload::Shown(Box::new(counter.clone())).into_tagged_box();
+        assert_eq!(Rc::strong_count(&counter), 2);
+        unsafe {
+            assert_eq!(format!("{:?}", shown.as_unsized_ref::<dyn Debug>()), "()");
+        }
+        match Payload::from_tagged_box(shown) {
+            Payload::Shown(shown) => assert_eq!(format!("{:?}", shown), "()"),
+            _ => unreachable!(),
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let zst = Payload::Shown(Box::new(())).into_tagged_box();
+        match Payload::from_tagged_box(zst) {
+            Payload::Shown(shown) => assert_eq!(format!("{:?}", shown), "()"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn inline_payloads() {
+        tagged_box! {
+            #[derive(Debug, Clone, PartialEq)]
+            struct Container, enum Item {
+                #[inline]
+                Integer(i32),
+            }
+        }
+
+        tagged_box! {
+            #[tagged_box(free_bits = 13, low_bits = 3)]
+            #[derive(Debug, Clone, PartialEq)]
+            struct Wide, enum WideItem {
+                #[inline]
+                Integer(i32),
+                #[inline]
+                Bytes([u8; 5]),
+            }
+        }
+
+        assert_eq!(TaggedBox::<Item>::INLINE_BITS, 59);
+        assert_eq!(TaggedBox::<WideItem, 13, 3>::INLINE_BITS, 47);
+
+        unsafe {
+            // Zero values are never null, as the lowest free bit is always set
+            let zero = TaggedBox::<Item>::new_inline(0i32, 0);
+            assert_eq!(zero.as_inline::<i32>(), 0);
+            assert_ne!(zero.as_u64(), 0);
+            TaggedBox::into_inline::<i32>(zero);
+
+            let bytes = TaggedBox::<WideItem, 13, 3>::new_inline([0xFFu8; 5], 1);
+            assert_eq!(bytes.discriminant(), 1);
+            assert_eq!(bytes.as_inline::<[u8; 5]>(), [0xFF; 5]);
+            assert_eq!(bytes.as_u64() & 0b111, 0);
+            assert_eq!(WideItem::from_tagged_box(bytes), WideItem::Bytes([0xFF; 5]));
+
+            let min = TaggedBox::<WideItem, 13, 3>::new_inline(i32::MIN, 0);
+            assert_eq!(min.as_inline::<i32>(), i32::MIN);
+            assert_eq!(WideItem::from_tagged_box(min), WideItem::Integer(i32::MIN));
+        }
+    }
+
+    #[test]
+    fn downcasts() {
+        tagged_box! {
+            #[derive(Debug, Clone, PartialEq)]
+            struct Container, enum Item {
+                Text(String),
+                Pair(u8, u16),
+                #[inline]
+                Short(i16),
+                Nothing,
+            }
+        }
+
+        let mut text = Item::Text(String::from("Downcast")).into_tagged_box();
+        assert_eq!(text.variant_type(), Some(VariantType::of::<String>()));
+        assert!(text.is::<String>());
+        assert!(!text.is::<u8>());
+        assert_eq!(text.downcast_ref::<String>().unwrap(), "Downcast");
+        assert_eq!(text.downcast_ref::<&str>(), None);
+
+        text.downcast_mut::<String>().unwrap().push_str(" me");
+        assert_eq!(text.downcast_mut::<usize>(), None);
+
+        let text = TaggedBox::downcast::<Vec<u8>>(text).unwrap_err();
+        assert_eq!(TaggedBox::downcast::<String>(text).unwrap(), "Downcast me");
+
+        // Multi-field variants store a type that can't be named outside of the macro
+        let pair = Item::Pair(1, 2).into_tagged_box();
+        assert_eq!(
+            pair.variant_type().map(|variant| variant.layout()),
+            Some(Layout::new::<[u16; 2]>()),
+        );
+        assert!(!pair.is::<(u8, u16)>());
+        assert_eq!(Item::from_tagged_box(pair), Item::Pair(1, 2));
+
+        // Inline variants aren't behind the pointer
+        let short = Item::Short(-1).into_tagged_box();
+        assert_eq!(short.variant_type(), None);
+        assert_eq!(short.downcast_ref::<i16>(), None);
+        let short = TaggedBox::downcast::<i16>(short).unwrap_err();
+        assert_eq!(Item::from_tagged_box(short), Item::Short(-1));
+
+        let nothing = Item::Nothing.into_tagged_box();
+        assert_eq!(nothing.downcast_ref::<()>(), Some(&()));
+        assert_eq!(TaggedBox::downcast::<()>(nothing).ok(), Some(()));
+
+        // Discriminants outside of the enum have no type
+        assert_eq!(<Item as TaggableInner>::variant_type(4), None);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        tagged_box! {
+            #[tagged_box(allocator = NotSync)]
+            struct Container, enum Item {
+                Text(String),
+                Integer(i32),
+            }
+        }
+
+        // Allocators are only a type, so they don't affect thread safety
+        #[allow(dead_code)]
+        struct NotSync(*const ());
+
+        unsafe impl RawAllocator for NotSync {
+            unsafe fn alloc(layout: Layout) -> *mut u8 {
+                Global::alloc(layout)
+            }
+
+            unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+                Global::dealloc(ptr, layout)
+            }
+        }
+
+        assert_send_sync::<Container>();
+        assert_send_sync::<TaggedBox<Item, DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS, NotSync>>();
+    }
 }
@@ -326,38 +326,38 @@ macro_rules! generate_tuple_arm_expansion {
         generate_tuple_arm_expansion! {
             @inner ($dollar, $others)
                 [
-                // ($tagged:expr, $enum:ident, $counter:ident, $variant:ident [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-                //     $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+                // ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+                //     $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
                 //          [
                 //              $( $finished )*
-                //              $enum::$variant() => $crate::TaggedBox::dangling::<$enum>($counter::$variant as u8),
+                //              $enum::$variant() => $crate::__taggable_into_box!(@dangling $boxed, $new, $enum, $counter::$variant as $crate::discriminant::Discriminant),
                 //         ]
                 //         $( $rest )*
                 //     )
                 // };
-                ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar variant:ident [$dollar ($dollar finished:tt)*] [$dollar ($dollar rest:tt)*] $dollar($dollar ty:ty),*) => {
-                    $dollar crate::__taggable_into_box!(@inner $dollar tagged, $dollar enum, $dollar counter
+                ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar boxed:ident, $dollar new:tt, $dollar variant:ident [$dollar ($dollar finished:tt)*] [$dollar ($dollar rest:tt)*] $dollar($dollar ty:ty),*) => {
+                    $dollar crate::__taggable_into_box!(@inner $dollar tagged, $dollar enum, $dollar counter, $dollar boxed, $dollar new
                             [
                             $dollar ( $dollar finished )*
-                            $dollar enum::$dollar variant() => $dollar crate::TaggedBox::dangling::<$dollar enum>($dollar counter::$dollar variant as u8),
+                            $dollar enum::$dollar variant() => $dollar crate::__taggable_into_box!(@dangling $dollar boxed, $dollar new, $dollar enum, $dollar counter::$dollar variant as $dollar crate::discriminant::Discriminant),
                         ]
                         $dollar ( $dollar rest )*
                 )
                 };
-                // ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-                //     $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+                // ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+                //     $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
                 //          [
                 //              $( $finished )*
-                //              $enum::$variant() => $crate::TaggedBox::<$enum>::dangling::<$enum>($counter::$variant as u8),
+                //              $enum::$variant() => $crate::__taggable_into_box!(@dangling $boxed, $new, $enum, $counter::$variant as $crate::discriminant::Discriminant),
                 //         ]
                 //         $( $rest )*
                 //     )
                 // };
-                ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar variant:ident, [$dollar ($dollar finished:tt)*] [$dollar ($dollar rest:tt)*] $dollar($dollar ty:ty),*) => {
-                    $dollar crate::__taggable_into_box!(@inner $dollar tagged, $dollar enum, $dollar counter
+                ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar boxed:ident, $dollar new:tt, $dollar variant:ident, [$dollar ($dollar finished:tt)*] [$dollar ($dollar rest:tt)*] $dollar($dollar ty:ty),*) => {
+                    $dollar crate::__taggable_into_box!(@inner $dollar tagged, $dollar enum, $dollar counter, $dollar boxed, $dollar new
                             [
                             $dollar ( $dollar finished )*
-                            $dollar enum::$dollar variant() => $dollar crate::TaggedBox::<$dollar enum>::dangling::<$dollar enum>($dollar counter::$dollar variant as u8),
+                            $dollar enum::$dollar variant() => $dollar crate::__taggable_into_box!(@dangling $dollar boxed, $dollar new, $dollar enum, $dollar counter::$dollar variant as $dollar crate::discriminant::Discriminant),
                         ]
                         $dollar ( $dollar rest )*
                 )
@@ -372,29 +372,29 @@ macro_rules! generate_tuple_arm_expansion {
             @inner ($dollar, $others)
                 [
                     $( $finished )*
-                // ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-                //     $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+                // ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+                //     $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
                 //          [
                 //              $( $finished )*
                 //             $enum::$variant(a, b, c) => {
                 //                  #[repr(C)]
                 //                  struct $variant($( $ty ),*);
                 //
-                //                  $crate::TaggedBox::<$enum>::new($variant(a, b, c), $counter::$variant as u8)
+                //                  $crate::$boxed::$new($variant(a, b, c), $counter::$variant as $crate::discriminant::Discriminant)
                 //              }
                 //         ]
                 //         $( $rest )*
                 //     )
                 // };
-                ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar variant:ident, $( $match )* $dollar $ident:ty [$dollar ($dollar finished:tt)*] [$dollar ($dollar rest:tt)*] $dollar($dollar ty:ty),*) => {
-                    $dollar crate::__taggable_into_box!(@inner $dollar tagged, $dollar enum, $dollar counter
+                ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar boxed:ident, $dollar new:tt, $dollar variant:ident, $( $match )* $dollar $ident:ty [$dollar ($dollar finished:tt)*] [$dollar ($dollar rest:tt)*] $dollar($dollar ty:ty),*) => {
+                    $dollar crate::__taggable_into_box!(@inner $dollar tagged, $dollar enum, $dollar counter, $dollar boxed, $dollar new
                             [
                             $dollar ( $dollar finished )*
                             $dollar enum::$dollar variant($( $access, )* $ident) => {
                                 #[repr(C)]
                                 struct $dollar variant($dollar ($dollar ty),*);
 
-                                $dollar crate::TaggedBox::<$dollar enum>::new::<$dollar variant>($dollar variant($( $access, )* $ident), $dollar counter::$dollar variant as _)
+                                $dollar crate::__taggable_into_box!(@new $dollar boxed, $dollar new, $dollar variant, $dollar variant($( $access, )* $ident), $dollar counter::$dollar variant as _)
                             }
                         ]
                         $dollar ( $dollar rest )*
@@ -414,7 +414,7 @@ macro_rules! generate_tuple_arm_expansion {
             // ($variant:path, $tuple:expr, $($others:ty),* [$($finished_match:tt)*] [$($rest_match:tt)*]) => {
             //      compile_error!("Only enum tuple variants of up to 32 elements are supported");
             // };
-            ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar variant:ident, $dollar ( $dollar $others:ty),* [$dollar($dollar finished:tt)*] [$dollar($dollar rest:tt)*]) => {
+            ($dollar tagged:expr, $dollar enum:ident, $dollar counter:ident, $dollar boxed:ident, $dollar new:tt, $dollar variant:ident, $dollar ( $dollar $others:ty),* [$dollar($dollar finished:tt)*] [$dollar($dollar rest:tt)*]) => {
                 compile_error!("Only enum tuple variants of up to 32 elements are supported");
             };
         }
@@ -443,473 +443,500 @@ macro_rules! generate_tuple_arm_expansion {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __expand_tuple_arm {
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident[$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident[$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
             [
                 $( $finished )*
-                $enum::$variant() => $crate::TaggedBox::<$enum>::dangling::<$enum>($counter::$variant as u8),
+                $enum::$variant() => $crate::__taggable_into_box!(@dangling $boxed, $new, $enum, $counter::$variant as $crate::discriminant::Discriminant),
             ]
             $( $rest )*
         )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident,[$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident,[$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
             [
                 $( $finished )*
-                $enum::$variant() => $crate::TaggedBox::<$enum>::dangling::<$enum>($counter::$variant as u8),
+                $enum::$variant() => $crate::__taggable_into_box!(@dangling $boxed, $new, $enum, $counter::$variant as $crate::discriminant::Discriminant),
             ]
             $( $rest )*
         )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty, $dd:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty, $dd:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty, $dd:ty, $ee:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty, $dd:ty, $ee:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd,ee) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd,ee), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd,ee), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty, $dd:ty, $ee:ty, $ff:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
-      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $a:ty, $b:ty, $c:ty, $d:ty, $e:ty, $f:ty, $g:ty, $h:ty, $i:ty, $j:ty, $k:ty, $l:ty, $m:ty, $n:ty, $o:ty, $p:ty, $q:ty, $r:ty, $s:ty, $t:ty, $u:ty, $v:ty, $w:ty, $x:ty, $y:ty, $z:ty, $aa:ty, $bb:ty, $cc:ty, $dd:ty, $ee:ty, $ff:ty [$($finished:tt)*] [$($rest:tt)*] $($ty:ty),*) => {
+      $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
         [
             $( $finished )*
             $enum::$variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd,ee,ff) => {
                 #[repr(C)]
                 struct $variant($( $ty ),*);
 
-                $crate::TaggedBox::new::<$variant>($variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd,ee,ff), $counter::$variant as u8)
+                $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant(a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w,x,y,z,aa,bb,cc,dd,ee,ff), $counter::$variant as $crate::discriminant::Discriminant)
             }
         ]
         $( $rest )*
       )
     };
-    ($tagged:expr, $enum:ident, $counter:ident, $variant:ident, $($others:ty),*[$($finished:tt)*] [$($rest:tt)*]) => {
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $variant:ident, $($others:ty),*[$($finished:tt)*] [$($rest:tt)*]) => {
       compile_error!("Only enum tuple variants of up to 32 elements are supported");
     };
   }
+
+// Binds every field of the tuple struct `$struct` behind `$expr` to a name taken from the pool and
+// builds `$variant` from them. When `$expr` is a reference the fields are bound by reference, which
+// lets the borrowed views point at each field in place
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __expand_tuple_view {
+    ($variant:path, $struct:ident, $expr:expr, $($ty:ty),*) => {
+        $crate::__expand_tuple_view!(@inner $variant, $struct, $expr, []
+            [a b c d e f g h i j k l m n o p q r s t u v w x y z aa bb cc dd ee ff]
+            $( $ty, )*
+        )
+    };
+
+    (@inner $variant:path, $struct:ident, $expr:expr, [$($bound:ident)*] [$next:ident $($pool:ident)*] $ty:ty, $($rest:ty,)*) => {
+        $crate::__expand_tuple_view!(@inner $variant, $struct, $expr, [$( $bound )* $next] [$( $pool )*] $( $rest, )*)
+    };
+
+    (@inner $variant:path, $struct:ident, $expr:expr, [$($bound:ident)*] [$($pool:ident)*]) => {{
+        let $struct($( $bound ),*) = $expr;
+        $variant($( $bound ),*)
+    }};
+
+    (@inner $variant:path, $struct:ident, $expr:expr, [$($bound:ident)*] [] $($rest:ty,)+) => {
+        compile_error!("Only enum tuple variants of up to 32 elements are supported");
+    };
+}
@@ -7,6 +7,10 @@ macro_rules! __make_enum {
         $crate::__make_enum! { @inner $vis, $enum, $( $meta ),* [] $( $rest )* }
     };
 
+    (@inner $vis:vis, $enum:ident, $($meta:meta),* [$($finished:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__make_enum! { @inner $vis, $enum, $( $meta ),* [$( $finished )*] $variant($ty), $( $rest )* }
+    };
+
     (@inner $vis:vis, $enum:ident, $($meta:meta),* [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
         $crate::__make_enum! { @inner $vis, $enum, $( $meta ),* [$( $finished )* $variant($( $ty ),*),] $( $rest )* }
     };
@@ -33,28 +37,102 @@ macro_rules! __make_enum {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __taggable_into_box {
-    ($tagged:expr, $enum:ident, $counter:ident, $($rest:tt)*) => {
-        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter [] $( $rest )*)
+    // `$new` is either `new`, `try_new` or `(recycle $recycled)`, which writes the variant into the
+    // allocation left in `$recycled` when its layout fits
+    (@new TaggedBox, (recycle $recycled:ident), $ty:ty, $value:expr, $discriminant:expr) => {
+        unsafe { $crate::TaggedBox::new_recycled::<$ty>($value, $discriminant, $recycled) }
+    };
+    // `Copy` containers only hold inline and unit variants, anything else is rejected by `__verify_copy`
+    (@new TaggedInline, $new:tt, $ty:ty, $value:expr, $discriminant:expr) => {
+        unreachable!()
+    };
+    // Safety: The discriminants and the enum variants are generated together, so every variant is
+    // stored as the type the enum gives its discriminant
+    (@new $boxed:ident, new, $ty:ty, $value:expr, $discriminant:expr) => {
+        unsafe { $crate::$boxed::new::<$ty>($value, $discriminant) }
+    };
+    // The discriminants are checked at compile time, so allocating is the only thing that can fail
+    (@new $boxed:ident, try_new, $ty:ty, $value:expr, $discriminant:expr) => {
+        unsafe { $crate::$boxed::try_new::<$ty>($value, $discriminant) }
+            .map_err($crate::TagError::into_alloc_error)
+    };
+
+    // Unit variants aren't allocated by boxes
+    (@dangling TaggedBox, new, $enum:ident, $discriminant:expr) => {
+        unsafe { $crate::TaggedBox::dangling::<$enum>($discriminant) }
+    };
+    (@dangling TaggedBox, try_new, $enum:ident, $discriminant:expr) => {
+        core::result::Result::<_, $crate::AllocError>::Ok(unsafe { $crate::TaggedBox::dangling::<$enum>($discriminant) })
+    };
+    (@dangling TaggedBox, (recycle $recycled:ident), $enum:ident, $discriminant:expr) => {
+        unsafe { $crate::TaggedBox::dangling::<$enum>($discriminant) }
+    };
+    (@dangling TaggedInline, new, $enum:ident, $discriminant:expr) => {
+        unsafe { $crate::TaggedInline::dangling::<$enum>($discriminant) }
+    };
+    (@dangling TaggedInline, try_new, $enum:ident, $discriminant:expr) => {
+        core::result::Result::<_, $crate::AllocError>::Ok(unsafe { $crate::TaggedInline::dangling::<$enum>($discriminant) })
+    };
+    // Shared pointers always need an allocation to hold their reference counts
+    (@dangling $boxed:ident, $new:tt, $enum:ident, $discriminant:expr) => {
+        $crate::__taggable_into_box!(@new $boxed, $new, (), (), $discriminant)
+    };
+
+    // Inline variants are stored in the pointer, which only boxes support
+    // Safety: `#[inline]` variants are stored inline by the generated `TaggableInner` impl
+    (@inline TaggedBox, new, $value:expr, $ty:ty, $discriminant:expr) => {
+        unsafe { $crate::TaggedBox::new_inline::<$ty>($value, $discriminant) }
+    };
+    (@inline TaggedBox, try_new, $value:expr, $ty:ty, $discriminant:expr) => {
+        core::result::Result::<_, $crate::AllocError>::Ok(unsafe { $crate::TaggedBox::new_inline::<$ty>($value, $discriminant) })
+    };
+    (@inline TaggedBox, (recycle $recycled:ident), $value:expr, $ty:ty, $discriminant:expr) => {
+        unsafe { $crate::TaggedBox::new_inline::<$ty>($value, $discriminant) }
+    };
+    (@inline TaggedInline, new, $value:expr, $ty:ty, $discriminant:expr) => {
+        unsafe { $crate::TaggedInline::new::<$ty>($value, $discriminant) }
+    };
+    (@inline TaggedInline, try_new, $value:expr, $ty:ty, $discriminant:expr) => {
+        core::result::Result::<_, $crate::AllocError>::Ok(unsafe { $crate::TaggedInline::new::<$ty>($value, $discriminant) })
+    };
+    (@inline $boxed:ident, $new:tt, $value:expr, $ty:ty, $discriminant:expr) => {
+        compile_error!("`#[inline]` variants can't be used in shared containers")
+    };
+
+    ($tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt, $($rest:tt)*) => {
+        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new [] $( $rest )*)
+    };
+
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($finished:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)* ) => {
+        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
+            [
+                $( $finished )*
+                $enum::$variant(var) => {
+                    $crate::__taggable_into_box!(@inline $boxed, $new, var, $ty, $counter::$variant as $crate::discriminant::Discriminant)
+                },
+            ]
+            $( $rest )*
+        )
     };
 
-    (@inner $tagged:expr, $enum:ident, $counter:ident [$($finished:tt)*] $variant:ident($ty:ty), $($rest:tt)* ) => {
-        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($finished:tt)*] $variant:ident($ty:ty), $($rest:tt)* ) => {
+        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
             [
                 $( $finished )*
                 $enum::$variant(var) => {
-                    $crate::TaggedBox::<$enum>::new::<$ty>(var, $counter::$variant as u8)
+                    $crate::__taggable_into_box!(@new $boxed, $new, $ty, var, $counter::$variant as $crate::discriminant::Discriminant)
                 },
             ]
             $( $rest )*
         )
     };
 
-    (@inner $tagged:expr, $enum:ident, $counter:ident [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)* ) => {
-        $crate::__expand_tuple_arm!($tagged, $enum, $counter, $variant, $( $ty ),* [$( $finished )*] [$( $rest )*] $( $ty ),*)
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)* ) => {
+        $crate::__expand_tuple_arm!($tagged, $enum, $counter, $boxed, $new, $variant, $( $ty ),* [$( $finished )*] [$( $rest )*] $( $ty ),*)
     };
 
-    (@inner $tagged:expr, $enum:ident, $counter:ident [$($tt:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)* ) => {
-        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($tt:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)* ) => {
+        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
             [
                 $( $tt )*
                 $enum::$variant { $( $member ),* } => {
@@ -62,14 +140,14 @@ macro_rules! __taggable_into_box {
                     struct $variant {
                         $( $member: $ty ),*
                     }
-                    $crate::TaggedBox::<$enum>::new::<$variant>($variant { $( $member ),* }, $counter::$variant as u8)
+                    $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant { $( $member ),* }, $counter::$variant as $crate::discriminant::Discriminant)
                 }
             ]
             $( $rest )*
         )
     };
-    (@inner $tagged:expr, $enum:ident, $counter:ident [$($tt:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)* ) => {
-        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($tt:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)* ) => {
+        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
             [
                 $( $tt )*
                 $enum::$variant { $( $member ),* } => {
@@ -77,24 +155,24 @@ macro_rules! __taggable_into_box {
                     struct $variant {
                         $( $member: $ty ),*
                     }
-                    $crate::TaggedBox::<$enum>::new::<$variant>($variant { $( $member ),* }, $counter::$variant as u8)
+                    $crate::__taggable_into_box!(@new $boxed, $new, $variant, $variant { $( $member ),* }, $counter::$variant as $crate::discriminant::Discriminant)
                 }
             ]
             $( $rest )*
         )
     };
 
-    (@inner $tagged:expr, $enum:ident, $counter:ident [$($finished:tt)*] $variant:ident, $($rest:tt)* ) => {
-        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($finished:tt)*] $variant:ident, $($rest:tt)* ) => {
+        $crate::__taggable_into_box!(@inner $tagged, $enum, $counter, $boxed, $new
             [
                 $( $finished )*
-                $enum::$variant => $crate::TaggedBox::<$enum>::dangling::<$enum>($counter::$variant as u8),
+                $enum::$variant => $crate::__taggable_into_box!(@dangling $boxed, $new, $enum, $counter::$variant as $crate::discriminant::Discriminant),
             ]
             $( $rest )*
         )
     };
 
-    (@inner $tagged:expr, $enum:ident, $counter:ident [$($finished:tt)*]) => {
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $boxed:ident, $new:tt [$($finished:tt)*]) => {
         match $tagged {
             $( $finished )*
         }
@@ -104,16 +182,15 @@ macro_rules! __taggable_into_box {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __derive_from {
+    (@inner $struct:ident, $enum:ident, #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__derive_from! { @inner $struct, $enum, $variant($ty), $( $rest )* }
+    };
+
     (@inner $struct:ident, $enum:ident, $variant:ident($ty:ty), $($rest:tt)*) => {
         impl From<$ty> for $struct {
             #[inline]
             fn from(val: $ty) -> Self {
-                #[allow(unused_imports)]
-                use $crate::TaggableInner;
-
-                Self {
-                    value: $enum::$variant(val).into_tagged_box(),
-                }
+                <$struct as From<$enum>>::from($enum::$variant(val))
             }
         }
 
@@ -124,13 +201,10 @@ macro_rules! __derive_from {
         impl From<($( $ty, )*)> for $struct {
             #[inline]
             fn from(tuple: ($( $ty, )*)) -> Self {
-                #![allow(unused_imports, unused_variables)]
-                use $crate::TaggableInner;
+                #![allow(unused_variables)]
 
                 let variant: $enum = $crate::__expand_tuple!($enum::$variant, tuple, $($ty),*);
-                Self {
-                    value: variant.into_tagged_box(),
-                }
+                <$struct as From<$enum>>::from(variant)
             }
         }
 
@@ -181,25 +255,28 @@ macro_rules! __generate_const_sizes {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __boxed_into_inner {
+    (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__boxed_into_inner!(@inner $tagged, $enum, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                $enum::$variant($crate::TaggedBox::into_inline::<$ty>($tagged))
+            },
+        ] $( $rest )*)
+    };
+
     (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
         $crate::__boxed_into_inner!(@inner $tagged, $enum, $counter, [
             $( $tt )*
-            discrim if discrim == $counter::$variant as u8 => {
-                // TODO: Inline variables
-                // if $crate::__generate_const_sizes!($ty) <= $crate::discriminant::DISCRIMINANT_BITS as usize {
-                //     todo!("Store small variables directly in the pointer")
-                // } else {
-                //     $enum::$variant($crate::TaggedBox::<$enum>::into_inner::<$ty>($tagged))
-                // }
-                $enum::$variant($crate::TaggedBox::<$enum>::into_inner::<$ty>($tagged))
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                $enum::$variant($crate::TaggedBox::into_inner::<$ty>($tagged))
             },
-        ] $( $rest )*);
+        ] $( $rest )*)
     };
 
     (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
         $crate::__boxed_into_inner!(@inner $tagged, $enum, $counter, [
             $( $tt )*
-            discrim if discrim == $counter::$variant as u8 => {
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                 #[allow(dead_code)]
                 #[repr(C)]
                 struct $variant($( $ty ),*);
@@ -208,47 +285,47 @@ macro_rules! __boxed_into_inner {
                 // if $crate::__generate_const_sizes!($( $ty ),*) <= $crate::discriminant::DISCRIMINANT_BITS as usize {
                 //     todo!("Store small variables directly in the pointer")
                 // } else {
-                //     $crate::__expand_tuple!($enum::$variant, $crate::TaggedBox::<$enum>::into_inner::<$variant>($tagged), $($ty),*)
+                //     $crate::__expand_tuple!($enum::$variant, $crate::TaggedBox::into_inner::<$variant>($tagged), $($ty),*)
                 // }
-                $crate::__expand_tuple!($enum::$variant, $crate::TaggedBox::<$enum>::into_inner::<$variant>($tagged), $($ty),*)
+                $crate::__expand_tuple!($enum::$variant, $crate::TaggedBox::into_inner::<$variant>($tagged), $($ty),*)
             },
-        ] $( $rest )*);
+        ] $( $rest )*)
     };
 
     (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty),* }, $($rest:tt)*) => {
         $crate::__boxed_into_inner!(@inner $tagged, $enum, $counter, [
             $( $tt )*
-            discrim if discrim == $counter::$variant as u8 => {
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                 // TODO: Miniscule pointer storage can be preformed here too
                 #[repr(C)]
                 struct $variant {
                     $( $ident: $ty ),*
                 }
-                let $variant { $( $ident ),* } = $crate::TaggedBox::<$enum>::into_inner::<$variant>($tagged);
+                let $variant { $( $ident ),* } = $crate::TaggedBox::into_inner::<$variant>($tagged);
                 $enum::$variant { $( $ident ),* }
             },
-        ] $( $rest )*);
+        ] $( $rest )*)
     };
     (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty,)* }, $($rest:tt)*) => {
         $crate::__boxed_into_inner!(@inner $tagged, $enum, $counter, [
             $( $tt )*
-            discrim if discrim == $counter::$variant as u8 => {
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                 // TODO: Miniscule pointer storage can be preformed here too
                 #[repr(C)]
                 struct $variant {
                     $( $ident: $ty ),*
                 }
-                let $variant { $( $ident ),* } = $crate::TaggedBox::<$enum>::into_inner::<$variant>($tagged);
+                let $variant { $( $ident ),* } = $crate::TaggedBox::into_inner::<$variant>($tagged);
                 $enum::$variant { $( $ident ),* }
             },
-        ] $( $rest )*);
+        ] $( $rest )*)
     };
 
     (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*] $variant:ident, $($rest:tt)*) => {
         $crate::__boxed_into_inner!(@inner $tagged, $enum, $counter, [
             $( $tt )*
-            discrim if discrim == $counter::$variant as u8 => $enum::$variant,
-        ] $( $rest )*);
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => $enum::$variant,
+        ] $( $rest )*)
     };
 
     (@inner $tagged:expr, $enum:ident, $counter:ident, [$($tt:tt)*]) => {
@@ -271,6 +348,22 @@ macro_rules! __from_tagged_box {
         $crate::__from_tagged_box!(@inner $tagged, $enum, $counter, $total_variants, [] $( $rest )*)
     };
 
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__from_tagged_box!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $($tt)*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    $enum::$variant($crate::TaggedBox::into_inline::<$ty>($tagged))
+                },
+            ] $($rest)*
+        )
+    };
+
     (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
         $crate::__from_tagged_box!(
             @inner
@@ -280,8 +373,8 @@ macro_rules! __from_tagged_box {
             $total_variants,
             [
                 $($tt)*
-                discrim if discrim == $counter::$variant as u8 => {
-                    $enum::$variant($crate::TaggedBox::<$enum>::into_inner::<$ty>($tagged))
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    $enum::$variant($crate::TaggedBox::into_inner::<$ty>($tagged))
                 },
             ] $($rest)*
         )
@@ -296,8 +389,12 @@ macro_rules! __from_tagged_box {
             $total_variants,
             [
                 $($tt)*
-                discrim if discrim == $counter::$variant as u8 => {
-                    $crate::__expand_tuple!($enum::$variant, $crate::TaggedBox::<$enum>::into_inner::<($( $ty ),*)>($tagged), $( $ty ),*)
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[allow(dead_code)]
+                    #[repr(C)]
+                    struct $variant($( $ty ),*);
+
+                    $crate::__expand_tuple!($enum::$variant, $crate::TaggedBox::into_inner::<$variant>($tagged), $( $ty ),*)
                 },
             ] $($rest)*
         )
@@ -312,12 +409,12 @@ macro_rules! __from_tagged_box {
             $total_variants,
             [
                 $($tt)*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     #[repr(C)]
                     struct $variant {
                         $( $ident: $ty ),*
                     }
-                    let $variant { $( $ident ),* } = $crate::TaggedBox::<$enum>::into_inner::<$variant>($tagged);
+                    let $variant { $( $ident ),* } = $crate::TaggedBox::into_inner::<$variant>($tagged);
                     $enum::$variant { $( $ident ),* }
                 },
             ] $($rest)*
@@ -332,12 +429,12 @@ macro_rules! __from_tagged_box {
             $total_variants,
             [
                 $($tt)*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     #[repr(C)]
                     struct $variant {
                         $( $ident: $ty ),*
                     }
-                    let $variant { $( $ident ),* } = $crate::TaggedBox::<$enum>::into_inner::<$variant>($tagged);
+                    let $variant { $( $ident ),* } = $crate::TaggedBox::into_inner::<$variant>($tagged);
                     $enum::$variant { $( $ident ),* }
                 },
             ] $($rest)*
@@ -353,7 +450,7 @@ macro_rules! __from_tagged_box {
             $total_variants,
             [
                 $($tt)*
-                discrim if discrim == $counter::$variant as u8 => $enum::$variant,
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => $enum::$variant,
             ] $($rest)*
         )
     };
@@ -364,7 +461,6 @@ macro_rules! __from_tagged_box {
             $( $tt )*
 
             discriminant => {
-                #[allow(non_upper_case_globals)]
                 panic!(
                     "The number of variants in `{}` is {}, but a variant by the discriminant of {} was attempted to be created",
                     stringify!($enum),
@@ -383,6 +479,24 @@ macro_rules! __ref_from_tagged {
         $crate::__ref_from_tagged!(@inner $tagged, $callback, $enum, $counter, $total_variants, [] $( $rest )*)
     };
 
+    (@inner $tagged:expr, $callback:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__ref_from_tagged!(
+            @inner
+            $tagged,
+            $callback,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $($tt)*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    let variant: core::mem::ManuallyDrop<$enum> = core::mem::ManuallyDrop::new($enum::$variant($tagged.as_inline::<$ty>()));
+                    ($callback)(&variant);
+                }
+            ] $($rest)*
+        )
+    };
+
     (@inner $tagged:expr, $callback:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
         $crate::__ref_from_tagged!(
             @inner
@@ -393,7 +507,7 @@ macro_rules! __ref_from_tagged {
             $total_variants,
             [
                 $($tt)*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     let variant: core::mem::ManuallyDrop<$enum> = core::mem::ManuallyDrop::new($enum::$variant($tagged.as_ptr::<$ty>().read()));
                     ($callback)(&variant);
                 }
@@ -411,7 +525,7 @@ macro_rules! __ref_from_tagged {
             $total_variants,
             [
                 $( $tt )*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     #[repr(C)]
                     struct $variant($( $ty ),*);
 
@@ -440,7 +554,7 @@ macro_rules! __ref_from_tagged {
             $total_variants,
             [
                 $( $tt )*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     #[repr(C)]
                     struct $variant {
                         $( $ident: $ty ),*
@@ -463,7 +577,7 @@ macro_rules! __ref_from_tagged {
             $total_variants,
             [
                 $( $tt )*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     #[repr(C)]
                     struct $variant {
                         $( $ident: $ty ),*
@@ -487,7 +601,7 @@ macro_rules! __ref_from_tagged {
             $total_variants,
             [
                 $( $tt )*
-                discrim if discrim == $counter::$variant as u8 => {
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
                     let variant: $enum = $enum::$variant;
                     ($callback)(&variant);
                 }
@@ -502,7 +616,6 @@ macro_rules! __ref_from_tagged {
             $( $rest )*
 
             discriminant => {
-                #[allow(non_upper_case_globals)]
                 panic!(
                     "The number of variants in `{}` is {}, but a variant by the discriminant of {} was attempted to be referenced",
                     stringify!($enum),
@@ -516,78 +629,691 @@ macro_rules! __ref_from_tagged {
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! __counter_enum {
-    (@inner [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
-        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+macro_rules! __drop_tagged {
+    ($tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, $($rest:tt)*) => {
+        $crate::__drop_tagged!(@inner $tagged, $enum, $counter, $total_variants, [] $( $rest )*)
     };
 
-    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)*) => {
-        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__drop_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                // Inline variants are never allocated and `Immediate` values are `Copy`, so there's nothing to drop
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {}
+            ]
+            $( $rest )*
+        )
     };
-    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)*) => {
-        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__drop_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    $crate::TaggedBox::drop_inner::<$ty>($tagged);
+                }
+            ]
+            $( $rest )*
+        )
     };
 
-    (@inner [$($finished:tt)*] $variant:ident, $($rest:tt)*) => {
-        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__drop_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[allow(dead_code)]
+                    #[repr(C)]
+                    struct $variant($( $ty ),*);
+
+                    $crate::TaggedBox::drop_inner::<$variant>($tagged);
+                }
+            ]
+            $( $rest )*
+        )
     };
 
-    (@inner [$($finished:tt)*]) => {
-        #[doc(hidden)]
-        #[allow(non_camel_case_types)]
-        enum __tagged_box_enum_counter {
-            $( $finished )*
-        }
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__drop_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[allow(dead_code)]
+                    #[repr(C)]
+                    struct $variant {
+                        $( $ident: $ty ),*
+                    }
+
+                    $crate::TaggedBox::drop_inner::<$variant>($tagged);
+                }
+            ]
+            $( $rest )*
+        )
     };
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__drop_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[allow(dead_code)]
+                    #[repr(C)]
+                    struct $variant {
+                        $( $ident: $ty ),*
+                    }
 
-    (@inner [$($finished:tt)*] $($tt:tt)*) => {
-        $crate::__counter_enum!(@error stringify!($( $tt )*))
+                    $crate::TaggedBox::drop_inner::<$variant>($tagged);
+                }
+            ]
+            $( $rest )*
+        )
     };
 
-    (@error $code:literal) => {
-        compile_error!("Invalid enum definition: {}", $literal);
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__drop_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                // Unit variants are never allocated, so there's nothing to drop
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {}
+            ]
+            $( $rest )*
+        )
     };
 
-    ($($rest:tt)*) => {
-        $crate::__counter_enum!(@inner [] $( $rest )*)
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($rest:tt)*]) => {
+        #[allow(unused_parens)]
+        match $tagged.discriminant() {
+            $( $rest )*
+
+            discriminant => {
+                panic!(
+                    "The number of variants in `{}` is {}, but a variant by the discriminant of {} was attempted to be dropped",
+                    stringify!($enum),
+                    $total_variants,
+                    discriminant
+                );
+            }
+        }
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! __count_variants {
-    (@inner [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
-        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+macro_rules! __clone_tagged {
+    ($tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, $($rest:tt)*) => {
+        $crate::__clone_tagged!(@inner $tagged, $enum, $counter, $total_variants, [] $( $rest )*)
     };
 
-    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)*) => {
-        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
-    };
-    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)*) => {
-        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__clone_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                // `Immediate` values are `Copy`, so the value is stored inline again
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    $crate::TaggedBox::new_inline::<$ty>($tagged.as_inline::<$ty>(), discrim)
+                }
+            ]
+            $( $rest )*
+        )
     };
 
-    (@inner [$($finished:tt)*] $variant:ident, $($rest:tt)*) => {
-        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__clone_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    $crate::TaggedBox::new::<$ty>(core::clone::Clone::clone(&*$tagged.as_ptr::<$ty>()), discrim)
+                }
+            ]
+            $( $rest )*
+        )
     };
 
-    (@inner [$($variant:ident,)*]) => {
-        [$( stringify!($variant) ),*].len()
-    };
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__clone_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[derive(Clone)]
+                    #[repr(C)]
+                    struct $variant($( $ty ),*);
 
-    ($($rest:tt)*) => {
-        $crate::__count_variants!(@inner [] $( $rest )*)
+                    $crate::TaggedBox::new::<$variant>(core::clone::Clone::clone(&*$tagged.as_ptr::<$variant>()), discrim)
+                }
+            ]
+            $( $rest )*
+        )
     };
-}
 
-// TODO: Finish verifier macro
-#[doc(hidden)]
-#[macro_export]
-macro_rules! __verify_variants {
-    ($variant:ident($($ty:ty),*), $($rest:tt)*) => {
-        $crate::__verify_variants!($( $rest )*)
-    };
-    ($variant:ident($($ty:ty,)*), $($rest:tt)*) => {
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__clone_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[derive(Clone)]
+                    #[repr(C)]
+                    struct $variant {
+                        $( $ident: $ty ),*
+                    }
+
+                    $crate::TaggedBox::new::<$variant>(core::clone::Clone::clone(&*$tagged.as_ptr::<$variant>()), discrim)
+                }
+            ]
+            $( $rest )*
+        )
+    };
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__clone_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    #[derive(Clone)]
+                    #[repr(C)]
+                    struct $variant {
+                        $( $ident: $ty ),*
+                    }
+
+                    $crate::TaggedBox::new::<$variant>(core::clone::Clone::clone(&*$tagged.as_ptr::<$variant>()), discrim)
+                }
+            ]
+            $( $rest )*
+        )
+    };
+
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($tt:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__clone_tagged!(
+            @inner
+            $tagged,
+            $enum,
+            $counter,
+            $total_variants,
+            [
+                $( $tt )*
+                // Unit variants are never allocated, so there's nothing to clone
+                discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                    $crate::TaggedBox::dangling::<$enum>(discrim)
+                }
+            ]
+            $( $rest )*
+        )
+    };
+
+    (@inner $tagged:expr, $enum:ident, $counter:ident, $total_variants:expr, [$($rest:tt)*]) => {
+        #[allow(unused_parens)]
+        match $tagged.discriminant() {
+            $( $rest )*
+
+            discriminant => {
+                panic!(
+                    "The number of variants in `{}` is {}, but a variant by the discriminant of {} was attempted to be cloned",
+                    stringify!($enum),
+                    $total_variants,
+                    discriminant
+                );
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __variant_types {
+    ($discriminant:expr, $counter:ident, $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [] $( $rest )*)
+    };
+
+    // Inline variants aren't stored behind the pointer, so they can't be downcast to
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => core::option::Option::None,
+        ] $( $rest )*)
+    };
+
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                core::option::Option::Some($crate::VariantType::of::<$ty>())
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                #[allow(dead_code)]
+                #[repr(C)]
+                struct $variant($( $ty ),*);
+
+                core::option::Option::Some($crate::VariantType::of::<$variant>())
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                #[allow(dead_code)]
+                #[repr(C)]
+                struct $variant {
+                    $( $ident: $ty ),*
+                }
+
+                core::option::Option::Some($crate::VariantType::of::<$variant>())
+            }
+        ] $( $rest )*)
+    };
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                #[allow(dead_code)]
+                #[repr(C)]
+                struct $variant {
+                    $( $ident: $ty ),*
+                }
+
+                core::option::Option::Some($crate::VariantType::of::<$variant>())
+            }
+        ] $( $rest )*)
+    };
+
+    // Unit variants are zero sized, so they're treated as holding a `()`
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__variant_types!(@inner $discriminant, $counter, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                core::option::Option::Some($crate::VariantType::of::<()>())
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $discriminant:expr, $counter:ident, [$($tt:tt)*]) => {
+        #[allow(unused_parens)]
+        match $discriminant {
+            $( $tt )*
+            _ => core::option::Option::None,
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __view_ty {
+    (ref, $ty:ty) => { &'a $ty };
+    (mut, $ty:ty) => { &'a mut $ty };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __make_view_enum {
+    ($kind:tt, $vis:vis, $view:ident, $($rest:tt)*) => {
+        $crate::__make_view_enum! { @inner $kind, $vis, $view, [] $( $rest )* }
+    };
+
+    // Inline variants aren't behind the pointer, so their value is copied out instead
+    (@inner $kind:tt, $vis:vis, $view:ident, [$($finished:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__make_view_enum! { @inner $kind, $vis, $view, [$( $finished )* $variant($ty),] $( $rest )* }
+    };
+
+    (@inner $kind:tt, $vis:vis, $view:ident, [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__make_view_enum! { @inner $kind, $vis, $view, [$( $finished )* $variant($( $crate::__view_ty!($kind, $ty) ),*),] $( $rest )* }
+    };
+
+    (@inner $kind:tt, $vis:vis, $view:ident, [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__make_view_enum! { @inner $kind, $vis, $view, [$( $finished )* $variant { $( $member: $crate::__view_ty!($kind, $ty) ),* },] $( $rest )* }
+    };
+    (@inner $kind:tt, $vis:vis, $view:ident, [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__make_view_enum! { @inner $kind, $vis, $view, [$( $finished )* $variant { $( $member: $crate::__view_ty!($kind, $ty) ),* },] $( $rest )* }
+    };
+
+    (@inner $kind:tt, $vis:vis, $view:ident, [$($finished:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__make_view_enum! { @inner $kind, $vis, $view, [$( $finished )* $variant,] $( $rest )* }
+    };
+
+    (@inner $kind:tt, $vis:vis, $view:ident, [$($finished:tt)*]) => {
+        $vis enum $view<'a> {
+            $( $finished )*
+
+            // Keeps `'a` used when no variant borrows anything, it can never be constructed
+            #[doc(hidden)]
+            __Lifetime(core::marker::PhantomData<&'a ()>, core::convert::Infallible),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __view_from_tagged {
+    (@ptr ref, $tagged:expr, $ty:ty) => {
+        &*$tagged.as_ptr::<$ty>()
+    };
+    (@ptr mut, $tagged:expr, $ty:ty) => {
+        &mut *$tagged.as_mut_ptr::<$ty>()
+    };
+
+    (@inline TaggedBox, $tagged:expr, $ty:ty) => {
+        $tagged.as_inline::<$ty>()
+    };
+    (@inline TaggedInline, $tagged:expr, $ty:ty) => {
+        $tagged.as_inline::<$ty>()
+    };
+    // Shared containers already reject inline variants
+    (@inline $pointer:ident, $tagged:expr, $ty:ty) => {
+        unreachable!()
+    };
+
+    ($kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [] $( $rest )*)
+    };
+
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                $view::$variant($crate::__view_from_tagged!(@inline $pointer, $tagged, $ty))
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                $view::$variant($crate::__view_from_tagged!(@ptr $kind, $tagged, $ty))
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                #[allow(dead_code)]
+                #[repr(C)]
+                struct $variant($( $ty ),*);
+
+                $crate::__expand_tuple_view!(
+                    $view::$variant,
+                    $variant,
+                    $crate::__view_from_tagged!(@ptr $kind, $tagged, $variant),
+                    $( $ty ),*
+                )
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                #[repr(C)]
+                struct $variant {
+                    $( $ident: $ty ),*
+                }
+
+                let $variant { $( $ident ),* } = $crate::__view_from_tagged!(@ptr $kind, $tagged, $variant);
+                $view::$variant { $( $ident ),* }
+            }
+        ] $( $rest )*)
+    };
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*] $variant:ident { $($ident:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => {
+                #[repr(C)]
+                struct $variant {
+                    $( $ident: $ty ),*
+                }
+
+                let $variant { $( $ident ),* } = $crate::__view_from_tagged!(@ptr $kind, $tagged, $variant);
+                $view::$variant { $( $ident ),* }
+            }
+        ] $( $rest )*)
+    };
+
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__view_from_tagged!(@inner $kind, $tagged, $view, $counter, $total_variants, $pointer, [
+            $( $tt )*
+            discrim if discrim == $counter::$variant as $crate::discriminant::Discriminant => $view::$variant,
+        ] $( $rest )*)
+    };
+
+    (@inner $kind:tt, $tagged:expr, $view:ident, $counter:ident, $total_variants:expr, $pointer:ident, [$($tt:tt)*]) => {
+        #[allow(unused_parens)]
+        match $tagged.discriminant() {
+            $( $tt )*
+
+            discriminant => {
+                panic!(
+                    "The number of variants in `{}` is {}, but a variant by the discriminant of {} was attempted to be borrowed",
+                    stringify!($view),
+                    $total_variants,
+                    discriminant
+                );
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tagged_box_views {
+    // Views are only generated when they're named with `#[tagged_box(ref = ..., mut = ...)]`
+    (@ref [], $($rest:tt)*) => {};
+    (@mut [], $($rest:tt)*) => {};
+
+    // Shared containers can't hand out mutable references, as other pointers may see the value
+    (@mut [$view:ident], TaggedRc, $($rest:tt)*) => {
+        compile_error!("`mut` views can't be used in shared containers");
+    };
+    (@mut [$view:ident], TaggedArc, $($rest:tt)*) => {
+        compile_error!("`mut` views can't be used in shared containers");
+    };
+
+    (@ref [$view:ident], $pointer:ident, $struct:ident, $struct_vis:vis, $enum_vis:vis, $($variants:tt)+) => {
+        $crate::__make_view_enum! { ref, $enum_vis, $view, $( $variants )+ }
+
+        impl $struct {
+            /// Borrows the variant stored in the container, referencing its fields in place
+            #[inline]
+            #[allow(dead_code, unused_unsafe, clippy::should_implement_trait)]
+            $struct_vis fn as_ref(&self) -> $view<'_> {
+                $crate::__counter_enum! {
+                    $( $variants )+
+                }
+
+                // Safety: The discriminants and the enum variants are generated together, so the type
+                // referenced for a discriminant is always the type that was allocated for it
+                const __TAGGED_BOX_TOTAL_VARIANTS: usize = $crate::__count_variants!($( $variants )+);
+                unsafe {
+                    $crate::__view_from_tagged!(ref, self.value, $view, __tagged_box_enum_counter, __TAGGED_BOX_TOTAL_VARIANTS, $pointer, $( $variants )+)
+                }
+            }
+        }
+    };
+
+    (@mut [$view:ident], $pointer:ident, $struct:ident, $struct_vis:vis, $enum_vis:vis, $($variants:tt)+) => {
+        $crate::__make_view_enum! { mut, $enum_vis, $view, $( $variants )+ }
+
+        impl $struct {
+            /// Mutably borrows the variant stored in the container, referencing its fields in place
+            #[inline]
+            #[allow(dead_code, unused_unsafe, clippy::should_implement_trait)]
+            $struct_vis fn as_mut(&mut self) -> $view<'_> {
+                $crate::__counter_enum! {
+                    $( $variants )+
+                }
+
+                // Safety: The discriminants and the enum variants are generated together, so the type
+                // referenced for a discriminant is always the type that was allocated for it
+                const __TAGGED_BOX_TOTAL_VARIANTS: usize = $crate::__count_variants!($( $variants )+);
+                unsafe {
+                    $crate::__view_from_tagged!(mut, self.value, $view, __tagged_box_enum_counter, __TAGGED_BOX_TOTAL_VARIANTS, $pointer, $( $variants )+)
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __counter_enum {
+    (@inner [$($finished:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__counter_enum!(@inner [$( $finished )*] $variant($ty), $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__counter_enum!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*]) => {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        enum __tagged_box_enum_counter {
+            $( $finished )*
+        }
+    };
+
+    (@inner [$($finished:tt)*] $($tt:tt)*) => {
+        $crate::__counter_enum!(@error stringify!($( $tt )*))
+    };
+
+    (@error $code:literal) => {
+        compile_error!("Invalid enum definition: {}", $literal);
+    };
+
+    ($($rest:tt)*) => {
+        $crate::__counter_enum!(@inner [] $( $rest )*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __count_variants {
+    (@inner [$($finished:tt)*] #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__count_variants!(@inner [$( $finished )*] $variant($ty), $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*] $variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty),* }, $($rest:tt)*) => {
+        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+    (@inner [$($finished:tt)*] $variant:ident { $($member:ident: $ty:ty,)* }, $($rest:tt)*) => {
+        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+
+    (@inner [$($finished:tt)*] $variant:ident, $($rest:tt)*) => {
+        $crate::__count_variants!(@inner [$( $finished )* $variant,] $( $rest )*)
+    };
+
+    (@inner [$($variant:ident,)*]) => {
+        [$( stringify!($variant) ),*].len()
+    };
+
+    ($($rest:tt)*) => {
+        $crate::__count_variants!(@inner [] $( $rest )*)
+    };
+}
+
+// Only inline and unit variants can be copied, as nothing else is stored in the pointer itself
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __verify_copy {
+    ($enum:ident, #[inline] $variant:ident($ty:ty), $($rest:tt)*) => {
+        $crate::__verify_copy!($enum, $( $rest )*);
+    };
+    ($enum:ident, $variant:ident, $($rest:tt)*) => {
+        $crate::__verify_copy!($enum, $( $rest )*);
+    };
+    ($enum:ident, $variant:ident $($fields:tt)?, $($rest:tt)*) => {
+        compile_error!(concat!(
+            "`", stringify!($enum), "` can only derive `Copy` when every variant is `#[inline]` or a unit, but `",
+            stringify!($variant), "` is allocated on the heap",
+        ));
+    };
+    ($enum:ident,) => {};
+}
+
+// TODO: Finish verifier macro
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __verify_variants {
+    ($variant:ident($($ty:ty),*), $($rest:tt)*) => {
+        $crate::__verify_variants!($( $rest )*)
+    };
+    ($variant:ident($($ty:ty,)*), $($rest:tt)*) => {
         $crate::__verify_variants!($( $rest )*)
     };
 }
@@ -596,142 +1322,785 @@ macro_rules! __verify_variants {
 /// can be used safely.  
 /// For more implementation details, see [manually implementing a tagged enum]
 ///
-/// # Example Usage
+/// # Example Usage
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// # use tagged_box::tagged_box;
+/// #
+/// tagged_box! {
+///     #[derive(Debug, Clone, PartialEq)] // This will be applied to both the inner enum and outer container
+///     struct Container, enum Item {
+///         Integer(i32),
+///         Numbers(usize, f32, u8),
+///         Unsigned {
+///             big: u128,
+///             large: u64,
+///             medium: u32,
+///             small: u16,
+///             tiny: u8,
+///         },
+///     }
+/// }
+/// ```
+///
+/// Note: The number of variants must be <= [`MAX_DISCRIMINANT`] + 1, which is checked at compile time
+///
+/// This will create a struct `Container` and an enum `Item`. Expanded, they will look like this:
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// # use tagged_box::{TaggableInner, TaggedBox};
+/// # use core::mem::ManuallyDrop;
+/// #
+/// #[derive(Debug, Clone, PartialEq)]
+/// #[repr(transparent)] // repr(transparent) is automatically added to the generated structs
+/// struct Container {
+///     value: TaggedBox<Item>,
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Item {
+///     Integer(i32),
+///     Boolean(bool),
+///     String(String),
+/// }
+///
+/// // ..Omitted some generated code
+/// # // Have to implement this to avoid compile error
+/// # impl TaggableInner for Item {
+/// #     fn into_tagged_box(self) -> TaggedBox<Self> {
+/// #         match self {
+/// #             Self::Integer(int) => unsafe { TaggedBox::new(int, 0) },
+/// #             Self::Boolean(boolean) => unsafe { TaggedBox::new(boolean, 1) },
+/// #             Self::String(string) => unsafe { TaggedBox::new(string, 2) },
+/// #         }
+/// #     }
+/// #     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
+/// #         unsafe {
+/// #             match tagged.discriminant() {
+/// #                 0 => Self::Integer(TaggedBox::into_inner(tagged)),
+/// #                 1 => Self::Boolean(TaggedBox::into_inner(tagged)),
+/// #                 2 => Self::String(TaggedBox::into_inner(tagged)),
+/// #                 _ => unreachable!(),
+/// #             }
+/// #         }
+/// #     }
+/// #     unsafe fn ref_from_tagged_box<F>(tagged: &TaggedBox<Self>, callback: F)
+/// #     where
+/// #         F: FnOnce(&Self),
+/// #     {
+/// #         let value = ManuallyDrop::new(match tagged.discriminant() {
+/// #             0 => Self::Integer(*tagged.as_ptr::<i32>()),
+/// #             1 => Self::Boolean(*tagged.as_ptr::<bool>()),
+/// #             2 => Self::String(tagged.as_ptr::<String>().read()),
+/// #             _ => unreachable!(),
+/// #         });
+/// #
+/// #         (callback)(&value);
+/// #     }
+/// #     unsafe fn drop_tagged_box(tagged: &mut TaggedBox<Self>) {
+/// #         match tagged.discriminant() {
+/// #             0 => TaggedBox::drop_inner::<i32>(tagged),
+/// #             1 => TaggedBox::drop_inner::<bool>(tagged),
+/// #             2 => TaggedBox::drop_inner::<String>(tagged),
+/// #             _ => unreachable!(),
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// The omitted code will contain `From` implementations that allow you to get a `Container` from any value that would
+/// be allowed to be inside of the `Item` enum, e.g.
+///
+/// ```compile_fail
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// # use tagged_box::tagged_box;
+/// # tagged_box! {
+/// #     #[derive(Debug, Clone, PartialEq)]
+/// #     struct Container, enum Item {
+/// #         Integer(i32),
+/// #         Boolean(bool),
+/// #         String(String),
+/// #     }
+/// # }
+/// #
+/// Container::from(10i32);         // Works!
+/// Container::from(String::new()); // Works!
+/// Container::from(Vec::new());    // Doesn't work :(
+/// ```
+///
+/// With your freshly created container, you can now store an enum on the stack with only `usize` bytes of memory and
+/// safely retrieve it.
+///
+/// To get the value of a `Container` instance, simply use [`into_inner`] after importing the [`TaggableContainer`] trait
 ///
 /// ```rust
 /// # extern crate alloc;
 /// # use alloc::string::String;
 /// # use tagged_box::tagged_box;
+/// # tagged_box! {
+/// #     #[derive(Debug, Clone, PartialEq)]
+/// #     struct Container, enum Item {
+/// #         Integer(i32),
+/// #         Boolean(bool),
+/// #         String(String),
+/// #     }
+/// # }
 /// #
+/// use tagged_box::TaggableContainer;
+///
+/// let container = Container::from(String::from("Hello from tagged-box!"));
+/// assert_eq!(container.into_inner(), Item::String(String::from("Hello from tagged-box!")));
+/// ```
+///
+/// # Free and Low Bits
+///
+/// By default the container uses the crate-wide reserved width set by `TAGGED_BOX_RESERVED_WIDTH`, but
+/// each container can choose its own number of free bits with `#[tagged_box(free_bits = N)]`. On 32bit
+/// targets there are no free upper bits, so containers default to the lower bits instead
+///
+/// ```rust
+/// # use tagged_box::{tagged_box, TaggableContainer};
+/// # #[cfg(target_pointer_width = "64")]
+/// # {
+/// tagged_box! {
+///     #[tagged_box(free_bits = 8)]
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         Integer(i32),
+///         Boolean(bool),
+///     }
+/// }
+///
+/// assert_eq!(Container::from(true).into_inner(), Item::Boolean(true));
+/// # }
+/// ```
+///
+/// The lower bits freed by alignment can be used as well with `#[tagged_box(low_bits = N)]`, every variant
+/// is then allocated with an alignment of at least `2 ^ N` bytes. Combined with the free upper bits, this
+/// gives wider discriminants without shrinking the usable address space
+///
+/// ```rust
+/// # use tagged_box::{tagged_box, TaggableContainer};
+/// # #[cfg(target_pointer_width = "64")]
+/// # {
+/// tagged_box! {
+///     // 4 upper and 3 lower bits, for a total of 128 possible variants
+///     #[tagged_box(free_bits = 4, low_bits = 3)]
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         Integer(i32),
+///         Boolean(bool),
+///     }
+/// }
+///
+/// assert_eq!(Container::from(10).into_inner(), Item::Integer(10));
+/// # }
+/// ```
+///
+/// Containers with more variants than their free bits can hold are rejected
+///
+/// ```compile_fail
+/// # use tagged_box::tagged_box;
+/// tagged_box! {
+///     #[tagged_box(free_bits = 1, low_bits = 0)]
+///     struct Container, enum Item {
+///         Integer(i32),
+///         Boolean(bool),
+///         Nothing,
+///     }
+/// }
+/// ```
+///
+/// # Allocators
+///
+/// Variants are allocated with the global allocator by default, any other [`RawAllocator`] can be
+/// used with `#[tagged_box(allocator = Type)]`
+///
+/// ```rust
+/// # use core::alloc::Layout;
+/// use tagged_box::{tagged_box, Global, RawAllocator, TaggableContainer};
+///
+/// struct Arena;
+///
+/// unsafe impl RawAllocator for Arena {
+///     unsafe fn alloc(layout: Layout) -> *mut u8 {
+///         // Hand out memory from the arena...
+/// #       Global::alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+///         // ...and give it back
+/// #       Global::dealloc(ptr, layout)
+///     }
+/// }
+///
+/// tagged_box! {
+///     #[tagged_box(allocator = Arena)]
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         Integer(i32),
+///         Boolean(bool),
+///     }
+/// }
+///
+/// assert_eq!(Container::from(10).into_inner(), Item::Integer(10));
+/// ```
+///
+/// Allocators have no instances, so every container of a type allocates from the same arena. When
+/// separate arenas are needed, such as one per VM, each one needs its own allocator type and with
+/// it its own container type. A const parameter can pick the arena out of a `static` table
+///
+/// ```rust
+/// # use core::alloc::Layout;
+/// use tagged_box::{tagged_box, Global, RawAllocator, TaggableContainer};
+///
+/// /// Allocates from the arena of the VM numbered `VM`
+/// struct VmArena<const VM: usize>;
+///
+/// unsafe impl<const VM: usize> RawAllocator for VmArena<VM> {
+///     unsafe fn alloc(layout: Layout) -> *mut u8 {
+///         // Hand out memory from `ARENAS[VM]`...
+/// #       Global::alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+///         // ...and give it back
+/// #       Global::dealloc(ptr, layout)
+///     }
+/// }
+///
+/// tagged_box! {
+///     #[tagged_box(allocator = VmArena<0>)]
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct FirstValue, enum FirstItem {
+///         Integer(i32),
+///     }
+/// }
+///
+/// tagged_box! {
+///     #[tagged_box(allocator = VmArena<1>)]
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct SecondValue, enum SecondItem {
+///         Integer(i32),
+///     }
+/// }
+///
+/// assert_eq!(FirstValue::from(10).into_inner(), FirstItem::Integer(10));
+/// assert_eq!(SecondValue::from(20).into_inner(), SecondItem::Integer(20));
+/// ```
+///
+/// Containers can also be created with `Container::try_from`, which returns an [`AllocError`] instead
+/// of aborting when the allocator runs out of memory
+///
+/// ```rust
+/// # use tagged_box::{tagged_box, TaggableContainer};
+/// # tagged_box! {
+/// #     #[derive(Debug, Clone, PartialEq)]
+/// #     struct Container, enum Item {
+/// #         Integer(i32),
+/// #         Boolean(bool),
+/// #     }
+/// # }
+/// let container = Container::try_from(Item::Boolean(true)).unwrap();
+/// assert_eq!(container.into_inner(), Item::Boolean(true));
+/// ```
+///
+/// # Inline variants
+///
+/// Single field tuple variants holding an [`Immediate`] type can be marked with `#[inline]`, storing
+/// their value directly in the pointer instead of allocating it. Whether the type fits into the
+/// pointer is checked at compile time, see [`TaggedBox::INLINE_BITS`]
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// use tagged_box::{tagged_box, TaggableContainer};
+///
+/// tagged_box! {
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         #[inline]
+///         Short(i16),
+///         #[inline]
+///         Boolean(bool),
+///         Text(String),
+///     }
+/// }
+///
+/// // Neither of these allocate
+/// assert_eq!(Container::from(5i16).into_inner(), Item::Short(5));
+/// assert_eq!(Container::from(true).into_inner(), Item::Boolean(true));
+/// ```
+///
+/// Inline variants can't be used in [shared containers], as those always point to an allocation
+///
+/// ```compile_fail
+/// # use tagged_box::tagged_box;
+/// tagged_box! {
+///     #[tagged_box(shared = Rc)]
+///     struct Container, enum Item {
+///         #[inline]
+///         Short(i16),
+///     }
+/// }
+/// ```
+///
+/// # In-place mutation
+///
+/// `Container::with_mut` runs a closure on a mutable reference to the variant without freeing it,
+/// see [`TaggableInner::mut_from_tagged_box`]. When the closure switches to another variant, the
+/// allocation is kept if the new variant has the same size and alignment
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::{string::String, vec::Vec};
+/// use tagged_box::{tagged_box, TaggableContainer};
+///
+/// tagged_box! {
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         Text(String),
+///         Bytes(Vec<u8>),
+///     }
+/// }
+///
+/// let mut container = Container::from(String::from("Hello"));
+/// container.with_mut(|item| {
+///     if let Item::Text(text) = item {
+///         *item = Item::Bytes(text.bytes().collect());
+///     }
+/// });
+///
+/// assert_eq!(container.into_inner(), Item::Bytes(b"Hello".to_vec()));
+/// ```
+///
+/// Shared containers don't own their variant, so `with_mut` is only generated for boxes
+///
+/// # Borrowed views
+///
+/// `#[tagged_box(ref = ItemRef, mut = ItemMut)]` generates enums mirroring the variants with every
+/// field replaced by a reference, along with `Container::as_ref` and `Container::as_mut` returning
+/// them. Unlike [`TaggableInner::ref_from_tagged_box`] the references point into the allocation
+/// itself, so nothing is copied and interior mutability works as expected. Either of the two can
+/// be left out, and `#[inline]` variants hold a copy of their value since they aren't allocated
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// use core::cell::Cell;
+/// use tagged_box::tagged_box;
+///
+/// tagged_box! {
+///     #[tagged_box(ref = ItemRef, mut = ItemMut)]
+///     struct Container, enum Item {
+///         Text(String),
+///         Counter(Cell<u32>),
+///         Nothing,
+///     }
+/// }
+///
+/// let mut container = Container::from(String::from("Hello"));
+/// if let ItemMut::Text(text) = container.as_mut() {
+///     text.push_str(" from tagged-box!");
+/// }
+///
+/// match container.as_ref() {
+///     ItemRef::Text(text) => assert_eq!(text, "Hello from tagged-box!"),
+///     ItemRef::Counter(counter) => counter.set(counter.get() + 1),
+///     ItemRef::Nothing => {}
+/// }
+/// ```
+///
+/// Shared containers only support `ref`, as other pointers may be looking at the same value
+///
+/// ```compile_fail
+/// # use tagged_box::tagged_box;
+/// tagged_box! {
+///     #[tagged_box(shared = Rc, mut = ItemMut)]
+///     struct Container, enum Item {
+///         Integer(i32),
+///     }
+/// }
+/// ```
+///
+/// # Checked downcasts
+///
+/// The generated [`TaggableInner`] impl knows the type stored for each variant, so the [`TaggedBox`]
+/// of a container can be downcast without `unsafe`. Single field tuple variants store their field,
+/// unit variants a `()` and `#[inline]` variants can't be downcast to, see [`TaggedBox::downcast_ref`]
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// use tagged_box::{tagged_box, TaggedBox, TaggableInner};
+///
+/// tagged_box! {
+///     struct Container, enum Item {
+///         Text(String),
+///         Integer(i32),
+///     }
+/// }
+///
+/// let tagged = Item::Integer(10).into_tagged_box();
+/// assert_eq!(tagged.downcast_ref::<i32>(), Some(&10));
+/// assert_eq!(tagged.downcast_ref::<String>(), None);
+/// ```
+///
+/// # Shared containers
+///
+/// With `#[tagged_box(shared = Rc)]` or `#[tagged_box(shared = Arc)]` the container holds a
+/// [`TaggedRc`] or [`TaggedArc`] instead of a [`TaggedBox`], so cloning it only increments a
+/// reference count instead of cloning the variant. Since other containers may still point to the
+/// same variant, [`into_inner`] clones it out and requires the enum to implement `Clone`
+///
+/// ```rust
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// use tagged_box::{tagged_box, TaggableContainer};
+///
+/// tagged_box! {
+///     #[tagged_box(shared = Arc)]
+///     #[derive(Debug, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         Text(String),
+///         Integer(i32),
+///     }
+/// }
+///
+/// let container = Container::from(String::from("Shared between containers"));
+/// let cloned = container.clone();
+///
+/// assert_eq!(container, cloned);
+/// assert_eq!(cloned.into_inner(), Item::Text(String::from("Shared between containers")));
+/// ```
+///
+/// # Copy containers
+///
+/// Containers own their variants, so copying one bit for bit would leave two containers freeing the
+/// same allocation. Only containers whose variants are all `#[inline]` or units can derive `Copy`, as
+/// nothing is allocated for them. They're backed by a [`TaggedInline`] instead of a [`TaggedBox`]
+///
+/// ```rust
+/// use tagged_box::{tagged_box, TaggableContainer};
+///
+/// tagged_box! {
+///     #[derive(Debug, Copy, Clone, PartialEq)]
+///     struct Container, enum Item {
+///         #[inline]
+///         Integer(i32),
+///         #[inline]
+///         Boolean(bool),
+///         Nothing,
+///     }
+/// }
+///
+/// let container = Container::from(10i32);
+/// let copied = container;
+///
+/// assert_eq!(container.into_inner(), Item::Integer(10));
+/// assert_eq!(copied.into_inner(), Item::Integer(10));
+/// ```
+///
+/// Any other container is rejected at compile time, they can still be cloned
+///
+/// ```compile_fail
+/// # extern crate alloc;
+/// # use alloc::string::String;
+/// use tagged_box::tagged_box;
+///
+/// tagged_box! {
+///     #[derive(Copy, Clone)]
+///     struct Container, enum Item {
+///         #[inline]
+///         Integer(i32),
+///         Text(String),
+///     }
+/// }
+/// ```
+///
+/// As are shared containers
+///
+/// ```compile_fail
+/// use tagged_box::tagged_box;
+///
 /// tagged_box! {
-///     #[derive(Debug, Clone, PartialEq)] // This will be applied to both the inner enum and outer container
+///     #[tagged_box(shared = Rc)]
+///     #[derive(Copy, Clone)]
 ///     struct Container, enum Item {
-///         Integer(i32),
-///         Numbers(usize, f32, u8),
-///         Unsigned {
-///             big: u128,
-///             large: u64,
-///             medium: u32,
-///             small: u16,
-///             tiny: u8,
-///         },
+///         Nothing,
 ///     }
 /// }
 /// ```
 ///
-/// Note: The number of variants must be <= [`MAX_DISCRIMINANT`]
+/// # Thread safety
 ///
-/// This will create a struct `Container` and an enum `Item`. Expanded, they will look like this:
+/// A container owns its variant the same way a `Box` would, so it's only `Send` or `Sync` when the
+/// enum is. Containers holding an `Rc` can't be sent to another thread
 ///
-/// ```rust
+/// ```compile_fail,E0277
 /// # extern crate alloc;
-/// # use alloc::string::String;
-/// # use tagged_box::{TaggableInner, TaggedBox};
-/// # use core::mem::ManuallyDrop;
-/// #
-/// #[derive(Debug, Clone, PartialEq)]
-/// #[repr(transparent)] // repr(transparent) is automatically added to the generated structs
-/// struct Container {
-///     value: TaggedBox<Item>,
-/// }
+/// # use alloc::rc::Rc;
+/// use tagged_box::tagged_box;
 ///
-/// #[derive(Debug, Clone, PartialEq)]
-/// enum Item {
-///     Integer(i32),
-///     Boolean(bool),
-///     String(String),
+/// tagged_box! {
+///     struct Container, enum Item {
+///         Shared(Rc<i32>),
+///         Integer(i32),
+///     }
 /// }
 ///
-/// // ..Omitted some generated code
-/// # // Have to implement this to avoid compile error
-/// # impl TaggableInner for Item {
-/// #     fn into_tagged_box(self) -> TaggedBox<Self> {
-/// #         match self {
-/// #             Self::Integer(int) => TaggedBox::new(int, 0),
-/// #             Self::Boolean(boolean) => TaggedBox::new(boolean, 1),
-/// #             Self::String(string) => TaggedBox::new(string, 2),
-/// #         }
-/// #     }
-/// #     fn from_tagged_box(tagged: TaggedBox<Self>) -> Self {
-/// #         unsafe {
-/// #             match tagged.discriminant() {
-/// #                 0 => Self::Integer(TaggedBox::into_inner(tagged)),
-/// #                 1 => Self::Boolean(TaggedBox::into_inner(tagged)),
-/// #                 2 => Self::String(TaggedBox::into_inner(tagged)),
-/// #                 _ => unreachable!(),
-/// #             }
-/// #         }
-/// #     }
-/// #     unsafe fn ref_from_tagged_box<F>(tagged: &TaggedBox<Self>, callback: F)
-/// #     where
-/// #         F: FnOnce(&Self),
-/// #     {
-/// #         let value = ManuallyDrop::new(match tagged.discriminant() {
-/// #             0 => Self::Integer(*tagged.as_ptr::<i32>()),
-/// #             1 => Self::Boolean(*tagged.as_ptr::<bool>()),
-/// #             2 => Self::String(tagged.as_ptr::<String>().read()),
-/// #             _ => unreachable!(),
-/// #         });
-/// #
-/// #         (callback)(&value);
-/// #     }
-/// # }
+/// let container = Container::from(10);
+/// std::thread::spawn(move || drop(container));
 /// ```
 ///
-/// The omitted code will contain `From` implementations that allow you to get a `Container` from any value that would
-/// be allowed to be inside of the `Item` enum, e.g.
+/// Or shared between threads
 ///
-/// ```compile_fail
+/// ```compile_fail,E0277
 /// # extern crate alloc;
-/// # use alloc::string::String;
-/// # use tagged_box::tagged_box;
-/// # tagged_box! {
-/// #     #[derive(Debug, Clone, PartialEq)]
-/// #     struct Container, enum Item {
-/// #         Integer(i32),
-/// #         Boolean(bool),
-/// #         String(String),
-/// #     }
-/// # }
-/// #
-/// Container::from(10i32);         // Works!
-/// Container::from(String::new()); // Works!
-/// Container::from(Vec::new());    // Doesn't work :(
+/// # use alloc::rc::Rc;
+/// use tagged_box::tagged_box;
+///
+/// tagged_box! {
+///     struct Container, enum Item {
+///         Shared(Rc<i32>),
+///         Integer(i32),
+///     }
+/// }
+///
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<Container>();
 /// ```
 ///
-/// With your freshly created container, you can now store an enum on the stack with only `usize` bytes of memory and
-/// safely retrieve it.
+/// `Rc` backed containers are never `Send`, even if the enum is, and `Arc` backed ones need the
+/// enum to be both `Send` and `Sync`, like [`TaggedArc`] itself
 ///
-/// To get the value of a `Container` instance, simply use [`into_inner`] after importing the [`TaggableContainer`] trait
+/// ```compile_fail,E0277
+/// use tagged_box::tagged_box;
 ///
-/// ```rust
-/// # extern crate alloc;
-/// # use alloc::string::String;
-/// # use tagged_box::tagged_box;
-/// # tagged_box! {
-/// #     #[derive(Debug, Clone, PartialEq)]
-/// #     struct Container, enum Item {
-/// #         Integer(i32),
-/// #         Boolean(bool),
-/// #         String(String),
-/// #     }
-/// # }
-/// #
-/// use tagged_box::TaggableContainer;
+/// tagged_box! {
+///     #[tagged_box(shared = Rc)]
+///     struct Container, enum Item {
+///         Integer(i32),
+///     }
+/// }
 ///
-/// let container = Container::from(String::from("Hello from tagged-box!"));
-/// assert_eq!(container.into_inner(), Item::String(String::from("Hello from tagged-box!")));
+/// let container = Container::from(10);
+/// std::thread::spawn(move || drop(container));
 /// ```
 ///
 /// [`TaggedBox`]: crate::TaggedBox
 /// [manually implementing a tagged enum]: crate::manually_impl_enum
-/// [`MAX_DISCRIMINANT`]: crate::discriminant::MAX_DISCRIMINANT
+/// [`TaggedInline`]: crate::TaggedInline
+/// [`RawAllocator`]: crate::RawAllocator
+/// [`TaggedRc`]: crate::rc::TaggedRc
+/// [`TaggedArc`]: crate::sync::TaggedArc
+/// [`AllocError`]: crate::AllocError
+/// [`Immediate`]: crate::Immediate
+/// [`TaggedBox::INLINE_BITS`]: crate::TaggedBox::INLINE_BITS
+/// [shared containers]: #shared-containers
+/// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
 /// [`into_inner`]: crate::TaggableContainer#into_inner
 /// [`TaggableContainer`]: crate::TaggableContainer
+/// [`TaggableInner`]: crate::TaggableInner
+/// [`TaggableInner::ref_from_tagged_box`]: crate::TaggableInner#ref_from_tagged_box
+/// [`TaggableInner::mut_from_tagged_box`]: crate::TaggableInner#mut_from_tagged_box
+/// [`TaggedBox::downcast_ref`]: crate::TaggedBox#downcast_ref
 #[macro_export]
 macro_rules! tagged_box {
+    ($($input:tt)+) => {
+        $crate::__tagged_box_parse! {
+            {
+                free_bits: { $crate::discriminant::DISCRIMINANT_BITS },
+                low_bits: { $crate::discriminant::LOW_DISCRIMINANT_BITS },
+                allocator: $crate::Global,
+                pointer: TaggedBox,
+                ref_view: [],
+                mut_view: [],
+                clone: []
+            }
+            []
+            $( $input )+
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tagged_box_parse {
+    // Boxes move their value out, shared pointers clone it as other pointers may still use it
+    (@into_inner TaggedBox, $value:expr, $enum:ident, $($variants:tt)+) => {{
+        $crate::__counter_enum! {
+            $( $variants )+
+        }
+
+        // Safety: The generated discriminants and their associated variants should be valid, as
+        // they are macro generated. As such, when calling `into_inner` the requested type should
+        // be valid for the tagged pointer
+        unsafe {
+            $crate::__boxed_into_inner!($value, $enum, __tagged_box_enum_counter, $( $variants )*)
+        }
+    }};
+    (@into_inner $pointer:ident, $value:expr, $enum:ident, $($variants:tt)+) => {
+        $value.to_inner()
+    };
+
+    // Only boxes own their variant outright, shared pointers may be looked at by other containers
+    (@with_mut TaggedBox, $struct:ident, $struct_vis:vis, $enum:ident) => {
+        impl $struct {
+            /// Runs a closure on a mutable reference to the variant stored in the container, keeping
+            /// its allocation when the variant it's left as has the same size and alignment
+            #[inline]
+            #[allow(dead_code)]
+            $struct_vis fn with_mut<F, R>(&mut self, callback: F) -> R
+            where
+                F: FnOnce(&mut $enum) -> R,
+            {
+                let mut result = core::option::Option::None;
+                $crate::TaggableInner::mut_from_tagged_box(&mut self.value, |variant| {
+                    result = core::option::Option::Some(callback(variant));
+                });
+
+                result.expect("`mut_from_tagged_box` didn't run its callback")
+            }
+        }
+    };
+    // Inline variants are copied out and stored again, so the container is left as it was on panic
+    (@with_mut TaggedInline, $struct:ident, $struct_vis:vis, $enum:ident) => {
+        impl $struct {
+            /// Runs a closure on a mutable reference to the variant stored in the container, storing
+            /// it back into the container afterwards
+            #[inline]
+            #[allow(dead_code)]
+            $struct_vis fn with_mut<F, R>(&mut self, callback: F) -> R
+            where
+                F: FnOnce(&mut $enum) -> R,
+            {
+                let mut variant = self.value.to_inner();
+                let result = callback(&mut variant);
+                *self = <Self as core::convert::From<$enum>>::from(variant);
+
+                result
+            }
+        }
+    };
+    (@with_mut $pointer:ident, $struct:ident, $struct_vis:vis, $enum:ident) => {};
+
+    // Enums that derive `Clone` clone their variants straight from the allocation, the others keep
+    // the default impl as their fields may not be `Clone`
+    (@clone [Clone], $enum:ident, $free_bits:tt, $low_bits:tt, $allocator:ty, $($variants:tt)+) => {
+        fn clone_tagged_box(tagged: &$crate::TaggedBox<Self, $free_bits, $low_bits, $allocator>) -> $crate::TaggedBox<Self, $free_bits, $low_bits, $allocator> {
+            $crate::__counter_enum! {
+                $( $variants )+
+            }
+
+            // Safety: The discriminants and the enum variants are generated together, so the type
+            // cloned for a discriminant is always the type that was allocated for it
+            const __TAGGED_BOX_TOTAL_VARIANTS: usize = $crate::__count_variants!($( $variants )+);
+            unsafe {
+                $crate::__clone_tagged!(tagged, $enum, __tagged_box_enum_counter, __TAGGED_BOX_TOTAL_VARIANTS, $( $variants )+)
+            }
+        }
+    };
+    (@clone [], $enum:ident, $free_bits:tt, $low_bits:tt, $allocator:ty, $($variants:tt)+) => {};
+
+    (@verify_copy TaggedInline, $enum:ident, $($variants:tt)+) => {
+        $crate::__verify_copy!($enum, $( $variants )+);
+    };
+    (@verify_copy $pointer:ident, $enum:ident, $($variants:tt)+) => {};
+
+    // `#[derive(Copy)]` backs the container with a `TaggedInline`, which can't be shared, and
+    // `#[derive(Clone)]` clones variants straight from their allocation
+    (@derive { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: TaggedBox, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] [$($derives:tt)*] (Copy $(, $($more:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @derive { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: TaggedInline, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] [$( $derives )*] ($( $( $more )* )?) $( $rest )+
+        }
+    };
+    (@derive { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: TaggedInline, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] [$($derives:tt)*] (Copy $(, $($more:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @derive { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: TaggedInline, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] [$( $derives )*] ($( $( $more )* )?) $( $rest )+
+        }
+    };
+    (@derive {$($config:tt)*} [$($meta:tt)*] [$($derives:tt)*] (Copy $(, $($more:tt)*)?) $($rest:tt)+) => {
+        compile_error!("Shared containers can't derive `Copy`, as their variants are reference counted");
+    };
+    (@derive { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $pointer:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] [$($derives:tt)*] (Clone $(, $($more:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @derive { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: $pointer, ref_view: $ref, mut_view: $mut, clone: [Clone] } [$( $meta )*] [$( $derives )*] ($( $( $more )* )?) $( $rest )+
+        }
+    };
+    (@derive {$($config:tt)*} [$($meta:tt)*] [$($derives:tt)*] ($derive:path $(, $($more:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! { @derive {$( $config )*} [$( $meta )*] [$( $derives )*] ($( $( $more )* )?) $( $rest )+ }
+    };
+    (@derive {$($config:tt)*} [$($meta:tt)*] [$($derives:tt)*] () $($rest:tt)+) => {
+        $crate::__tagged_box_parse! { {$( $config )*} [$( $meta )* derive($( $derives )*)] $( $rest )+ }
+    };
+
+    // Options given through `#[tagged_box(...)]`
+    (@options { free_bits: $old:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $pointer:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (free_bits = $free_bits:tt $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: $pointer, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $old:tt, allocator: $allocator:ty, pointer: $pointer:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (low_bits = $low_bits:tt $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: $pointer, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $old:ty, pointer: $pointer:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (allocator = $allocator:ty $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: $pointer, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: TaggedInline, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (shared = $shared:ident $(, $($options:tt)*)?) $($rest:tt)+) => {
+        compile_error!("Shared containers can't derive `Copy`, as their variants are reference counted");
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $old:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (shared = Rc $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: TaggedRc, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $old:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (shared = Arc $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: TaggedArc, ref_view: $ref, mut_view: $mut, clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $pointer:ident, ref_view: $old:tt, mut_view: $mut:tt, clone: $clone:tt } [$($meta:tt)*] (ref = $ref:ident $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: $pointer, ref_view: [$ref], mut_view: $mut, clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $pointer:ident, ref_view: $ref:tt, mut_view: $old:tt, clone: $clone:tt } [$($meta:tt)*] (mut = $mut:ident $(, $($options:tt)*)?) $($rest:tt)+) => {
+        $crate::__tagged_box_parse! {
+            @options { free_bits: $free_bits, low_bits: $low_bits, allocator: $allocator, pointer: $pointer, ref_view: $ref, mut_view: [$mut], clone: $clone } [$( $meta )*] ($( $( $options )* )?) $( $rest )+
+        }
+    };
+    (@options {$($config:tt)*} [$($meta:tt)*] () $($rest:tt)+) => {
+        $crate::__tagged_box_parse! { {$( $config )*} [$( $meta )*] $( $rest )+ }
+    };
+    (@options {$($config:tt)*} [$($meta:tt)*] ($($options:tt)*) $($rest:tt)+) => {
+        compile_error!(concat!("Invalid `tagged_box` option: ", stringify!($( $options )*)));
+    };
+
+    ({$($config:tt)*} [$($meta:tt)*] #[tagged_box($($options:tt)*)] $($rest:tt)+) => {
+        $crate::__tagged_box_parse! { @options {$( $config )*} [$( $meta )*] ($( $options )*) $( $rest )+ }
+    };
+    ({$($config:tt)*} [$($meta:tt)*] #[derive($($derives:tt)*)] $($rest:tt)+) => {
+        $crate::__tagged_box_parse! { @derive {$( $config )*} [$( $meta )*] [$( $derives )*] ($( $derives )*) $( $rest )+ }
+    };
+    ({$($config:tt)*} [$($meta:tt)*] #[$attr:meta] $($rest:tt)+) => {
+        $crate::__tagged_box_parse! { {$( $config )*} [$( $meta )* $attr] $( $rest )+ }
+    };
+
+    // Everything but the enum itself, which `#[derive(Taggable)]` enums declare on their own
     (
-        $( #[$meta:meta] )*
+        @generate
+        { free_bits: $free_bits:tt, low_bits: $low_bits:tt, allocator: $allocator:ty, pointer: $pointer:ident, ref_view: $ref:tt, mut_view: $mut:tt, clone: $clone:tt }
+        [$($meta:meta)*]
         $struct_vis:vis struct $struct:ident, $enum_vis:vis enum $enum:ident {
             $($variants:tt)+
         }
@@ -739,57 +2108,84 @@ macro_rules! tagged_box {
         $( #[$meta] )*
         #[repr(transparent)]
         $struct_vis struct $struct {
-            value: $crate::TaggedBox<$enum>,
+            value: $crate::$pointer<$enum, $free_bits, $low_bits, $allocator>,
         }
 
+        $crate::__tagged_box_parse!(@verify_copy $pointer, $enum, $( $variants )+);
+
+        const _: () = assert!(
+            $crate::__count_variants!($( $variants )+) <= $crate::TaggedPointer::<$free_bits, $low_bits>::MAX_DISCRIMINANT as usize + 1,
+            concat!("`", stringify!($enum), "` has more variants than can be stored in its free bits"),
+        );
         impl $crate::TaggableContainer for $struct {
             type Inner = $enum;
 
             fn into_inner(self) -> $enum {
+                $crate::__tagged_box_parse!(@into_inner $pointer, self.value, $enum, $( $variants )+)
+            }
+        }
+
+        impl From<$enum> for $struct {
+            #[inline]
+            fn from(variant: $enum) -> Self {
                 $crate::__counter_enum! {
                     $( $variants )+
                 }
 
-                // Safety: The generated discriminants and their associated variants should be valid, as
-                // they are macro generated. As such, when calling `into_inner` the requested type should
-                // be valid for the tagged pointer
-                unsafe {
-                    $crate::__boxed_into_inner!(self.value, $enum, __tagged_box_enum_counter, $( $variants )*)
+                Self {
+                    value: $crate::__taggable_into_box!(variant, $enum, __tagged_box_enum_counter, $pointer, new, $( $variants )+),
                 }
             }
         }
 
-        impl From<$enum> for $struct {
+        impl $struct {
+            /// Creates a container from a variant, returning an error instead of panicking
+            /// if the variant couldn't be allocated
             #[inline]
-            fn from(variant: $enum) -> Self {
-                use $crate::TaggableInner;
-
-                Self {
-                    value: variant.into_tagged_box(),
+            #[allow(dead_code)]
+            $struct_vis fn try_from(variant: $enum) -> core::result::Result<Self, $crate::AllocError> {
+                $crate::__counter_enum! {
+                    $( $variants )+
                 }
+
+                core::result::Result::Ok(Self {
+                    value: $crate::__taggable_into_box!(variant, $enum, __tagged_box_enum_counter, $pointer, try_new, $( $variants )+)?,
+                })
             }
         }
 
+        $crate::__tagged_box_parse!(@with_mut $pointer, $struct, $struct_vis, $enum);
+
         $crate::__derive_from! {
             $struct, $enum, $( $variants )+
         }
 
-        $crate::__make_enum! {
-            $enum_vis, $enum
-            [$( $meta )*]
-            $( $variants )+
+        $crate::__tagged_box_views! {
+            @ref $ref, $pointer, $struct, $struct_vis, $enum_vis, $( $variants )+
+        }
+
+        $crate::__tagged_box_views! {
+            @mut $mut, $pointer, $struct, $struct_vis, $enum_vis, $( $variants )+
         }
 
-        impl $crate::TaggableInner for $enum {
-            fn into_tagged_box(self) -> $crate::TaggedBox<Self> {
+        impl $crate::TaggableInner<$free_bits, $low_bits, $allocator> for $enum {
+            fn into_tagged_box(self) -> $crate::TaggedBox<Self, $free_bits, $low_bits, $allocator> {
+                $crate::__counter_enum! {
+                    $( $variants )+
+                }
+
+                $crate::__taggable_into_box!( self, $enum, __tagged_box_enum_counter, TaggedBox, new, $( $variants )+)
+            }
+
+            fn try_into_tagged_box(self) -> core::result::Result<$crate::TaggedBox<Self, $free_bits, $low_bits, $allocator>, $crate::AllocError> {
                 $crate::__counter_enum! {
                     $( $variants )+
                 }
 
-                $crate::__taggable_into_box!( self, $enum, __tagged_box_enum_counter, $( $variants )+)
+                $crate::__taggable_into_box!( self, $enum, __tagged_box_enum_counter, TaggedBox, try_new, $( $variants )+)
             }
 
-            fn from_tagged_box(tagged: $crate::TaggedBox<$enum>) -> Self {
+            fn from_tagged_box(tagged: $crate::TaggedBox<$enum, $free_bits, $low_bits, $allocator>) -> Self {
                 // Safety: The discriminants and the enum variants should be synced, as they are all
                 // generated by a macro. Therefore, when `tagged`'s discriminant and the current discriminant
                 // are the same, the variant should be valid for the data stored at `tagged`
@@ -809,7 +2205,7 @@ macro_rules! tagged_box {
                 }
             }
 
-            unsafe fn ref_from_tagged_box<F>(tagged: &$crate::TaggedBox<$enum>, callback: F)
+            unsafe fn ref_from_tagged_box<F>(tagged: &$crate::TaggedBox<$enum, $free_bits, $low_bits, $allocator>, callback: F)
             where
                 F: FnOnce(&$enum),
             {
@@ -827,6 +2223,77 @@ macro_rules! tagged_box {
                     $( $variants )*
                 );
             }
+
+            // Enums made only of inline and unit variants never reuse an allocation, and the variants
+            // are created in their own `unsafe` block
+            #[allow(unused_variables, unused_unsafe)]
+            fn mut_from_tagged_box<F>(tagged: &mut $crate::TaggedBox<$enum, $free_bits, $low_bits, $allocator>, callback: F)
+            where
+                F: FnOnce(&mut $enum),
+            {
+                $crate::__counter_enum! {
+                    $( $variants )+
+                }
+
+                // Safety: The generated `ref_from_tagged_box` reads the variant out bitwise and the type
+                // table holds the type allocated for every discriminant, so the old allocation is only
+                // reused by variants with the same layout
+                unsafe {
+                    $crate::TaggedBox::mutate_recycled(tagged, callback, |variant, recycled| {
+                        $crate::__taggable_into_box!(variant, $enum, __tagged_box_enum_counter, TaggedBox, (recycle recycled), $( $variants )+)
+                    });
+                }
+            }
+
+            unsafe fn drop_tagged_box(tagged: &mut $crate::TaggedBox<$enum, $free_bits, $low_bits, $allocator>) {
+                $crate::__counter_enum! {
+                    $( $variants )+
+                }
+
+                // Safety: The discriminants and the enum variants are generated together, so the type
+                // dropped for a discriminant is always the type that was allocated for it
+                const __TAGGED_BOX_TOTAL_VARIANTS: usize = $crate::__count_variants!($( $variants )+);
+                $crate::__drop_tagged!(
+                    tagged,
+                    $enum,
+                    __tagged_box_enum_counter,
+                    __TAGGED_BOX_TOTAL_VARIANTS,
+                    $( $variants )*
+                );
+            }
+
+            fn variant_type(discriminant: $crate::discriminant::Discriminant) -> core::option::Option<$crate::VariantType> {
+                $crate::__counter_enum! {
+                    $( $variants )+
+                }
+
+                $crate::__variant_types!(discriminant, __tagged_box_enum_counter, $( $variants )+)
+            }
+
+            $crate::__tagged_box_parse!(@clone $clone, $enum, $free_bits, $low_bits, $allocator, $( $variants )+);
+        }
+    };
+
+    (
+        {$($config:tt)*}
+        [$($meta:meta)*]
+        $struct_vis:vis struct $struct:ident, $enum_vis:vis enum $enum:ident {
+            $($variants:tt)+
+        }
+    ) => {
+        $crate::__tagged_box_parse! {
+            @generate
+            {$( $config )*}
+            [$( $meta )*]
+            $struct_vis struct $struct, $enum_vis enum $enum {
+                $( $variants )+
+            }
+        }
+
+        $crate::__make_enum! {
+            $enum_vis, $enum
+            [$( $meta )*]
+            $( $variants )+
         }
     };
 }
@@ -836,6 +2303,7 @@ mod tests {
     #[test]
     fn variants_compile() {
         tagged_box! {
+            #[allow(dead_code)]
             #[derive(Debug, Clone, PartialEq, Eq)]
             struct Container, enum Item {
                 Unit,
@@ -0,0 +1,322 @@
+use crate::{
+    allocator::{Global, RawAllocator},
+    discriminant::{Discriminant, DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    immediate::Immediate,
+    taggable::TaggableInner,
+    tagged_box::TaggedBox,
+    tagged_pointer::TaggedPointer,
+};
+use core::{
+    cmp, fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+};
+
+/// A [`TaggedBox`] that never owns an allocation, holding either an [`Immediate`] value stored in
+/// the pointer or a unit variant
+///
+/// As there's nothing to drop or free, a `TaggedInline` can be copied like the values it holds.
+/// This is what [`tagged_box!`] containers that derive `Copy` are backed by, which is only allowed
+/// when every variant is `#[inline]` or a unit
+///
+/// ```rust
+/// use tagged_box::{tagged_box, TaggedInline};
+///
+/// tagged_box! {
+///     #[derive(Debug, Copy, Clone, PartialEq)]
+///     struct Number, enum Numbers {
+///         #[inline]
+///         Short(i16),
+///         Nothing,
+///     }
+/// }
+///
+/// // Safety: `Numbers` stores its first variant inline as an `i16`
+/// let short: TaggedInline<Numbers> = unsafe { TaggedInline::new(-10i16, 0) };
+/// let copied = short;
+///
+/// assert_eq!(short.to_inner(), Numbers::Short(-10));
+/// assert_eq!(copied.to_inner(), Numbers::Short(-10));
+/// ```
+///
+/// Like [`TaggedBox`], `T` is the enum the value is a variant of, and every discriminant a
+/// `TaggedInline` is created with must be one that `T` stores inline or as a unit
+///
+/// [`TaggedBox`]: crate::TaggedBox
+/// [`Immediate`]: crate::Immediate
+/// [`tagged_box!`]: macro.tagged_box.html
+#[repr(transparent)]
+pub struct TaggedInline<
+    T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+    A: RawAllocator = Global,
+> {
+    boxed: TaggedPointer<FREE_BITS, LOW_BITS>,
+    _type: PhantomData<(T, A)>,
+}
+
+// Safety: A `TaggedInline` holds its value by value, so it can be moved or shared between threads
+// whenever `T` can, see `TaggedBox`
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Send
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Send,
+{
+}
+
+unsafe impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Sync
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Sync,
+{
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > TaggedInline<T, FREE_BITS, LOW_BITS, A>
+{
+    /// Creates a new `TaggedInline` that stores `val` directly in the pointer, see
+    /// [`TaggedBox::new_inline`] for more
+    ///
+    /// # Safety
+    ///
+    /// `T` must store the variant for `discriminant` inline as a `U`, as the value is read back
+    /// through `T` and [`into_tagged_box`] hands it out as a `TaggedBox` that `T` drops
+    ///
+    /// [`TaggedBox::new_inline`]: crate::TaggedBox#new_inline
+    /// [`into_tagged_box`]: crate::TaggedInline#into_tagged_box
+    #[inline]
+    pub unsafe fn new<U: Immediate>(val: U, discriminant: Discriminant) -> Self {
+        // The box was created by `new_inline`
+        Self::from_tagged_box(TaggedBox::new_inline(val, discriminant))
+    }
+
+    /// Creates a `TaggedInline` for a zero sized value, such as a unit variant, see
+    /// [`TaggedBox::dangling`] for more
+    ///
+    /// # Safety
+    ///
+    /// `T` must store a zero sized `U` or nothing at all for `discriminant` without allocating,
+    /// like the unit variants of [`tagged_box!`]. Otherwise the dangling pointer would be read
+    /// from by [`to_inner`] and freed by the `TaggedBox` that [`into_tagged_box`] returns
+    ///
+    /// [`TaggedBox::dangling`]: crate::TaggedBox#dangling
+    /// [`tagged_box!`]: macro.tagged_box.html
+    /// [`to_inner`]: crate::TaggedInline#to_inner
+    /// [`into_tagged_box`]: crate::TaggedInline#into_tagged_box
+    #[inline]
+    pub const unsafe fn dangling<U>(discriminant: Discriminant) -> Self {
+        Self {
+            boxed: TaggedPointer::<FREE_BITS, LOW_BITS>::dangling::<U>(discriminant),
+            _type: PhantomData,
+        }
+    }
+
+    /// Creates a `TaggedInline` from a [`TaggedBox`] that doesn't own an allocation
+    ///
+    /// # Safety
+    ///
+    /// `tagged` must have been created by [`TaggedBox::new_inline`] or [`TaggedBox::dangling`],
+    /// otherwise its allocation would be shared by every copy
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`TaggedBox::new_inline`]: crate::TaggedBox#new_inline
+    /// [`TaggedBox::dangling`]: crate::TaggedBox#dangling
+    #[inline]
+    pub unsafe fn from_tagged_box(tagged: TaggedBox<T, FREE_BITS, LOW_BITS, A>) -> Self {
+        Self {
+            boxed: TaggedBox::into_tagged_pointer(tagged),
+            _type: PhantomData,
+        }
+    }
+
+    /// Turns the `TaggedInline` back into a [`TaggedBox`], which can be used with the enum's
+    /// [`TaggableInner`] impl
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`TaggableInner`]: crate::TaggableInner
+    #[inline]
+    pub fn into_tagged_box(self) -> TaggedBox<T, FREE_BITS, LOW_BITS, A> {
+        TaggedBox::from_tagged_pointer(self.boxed)
+    }
+
+    /// Returns the discriminant of the stored value
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant {
+        self.boxed.discriminant()
+    }
+
+    /// Returns a copy of the value stored inline
+    ///
+    /// # Safety
+    ///
+    /// The `TaggedInline` must have been created with a `U`
+    #[inline]
+    pub unsafe fn as_inline<U: Immediate>(&self) -> U {
+        self.view().as_inline()
+    }
+
+    /// Copies the stored value out as its enum
+    #[inline]
+    pub fn to_inner(&self) -> T {
+        T::from_tagged_box(self.into_tagged_box())
+    }
+
+    /// Views the value as a [`TaggedBox`] so that the enum's [`TaggableInner`] impl can be used
+    /// on it, the box is never dropped
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    /// [`TaggableInner`]: crate::TaggableInner
+    #[inline]
+    fn view(&self) -> ManuallyDrop<TaggedBox<T, FREE_BITS, LOW_BITS, A>> {
+        ManuallyDrop::new(self.into_tagged_box())
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Clone for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<
+        T: TaggableInner<FREE_BITS, LOW_BITS, A>,
+        const FREE_BITS: u32,
+        const LOW_BITS: u32,
+        A: RawAllocator,
+    > Copy for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+{
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Debug
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_inner(), f)
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> fmt::Display
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_inner(), f)
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> PartialEq
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + PartialEq<T>,
+{
+    fn eq(&self, other: &TaggedInline<T, FREE_BITS, LOW_BITS, A>) -> bool {
+        self.to_inner() == other.to_inner()
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Eq
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Eq,
+{
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> PartialOrd
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + PartialOrd<T>,
+{
+    fn partial_cmp(
+        &self,
+        other: &TaggedInline<T, FREE_BITS, LOW_BITS, A>,
+    ) -> Option<cmp::Ordering> {
+        self.to_inner().partial_cmp(&other.to_inner())
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Ord
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Ord,
+{
+    fn cmp(&self, other: &TaggedInline<T, FREE_BITS, LOW_BITS, A>) -> cmp::Ordering {
+        self.to_inner().cmp(&other.to_inner())
+    }
+}
+
+impl<T, const FREE_BITS: u32, const LOW_BITS: u32, A: RawAllocator> Hash
+    for TaggedInline<T, FREE_BITS, LOW_BITS, A>
+where
+    T: TaggableInner<FREE_BITS, LOW_BITS, A> + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_inner().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem;
+
+    tagged_box! {
+        #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+        struct Container, enum Item {
+            #[inline]
+            Short(i16),
+            #[inline]
+            Flag(bool),
+            Nothing,
+        }
+    }
+
+    #[test]
+    fn copies() {
+        let short: TaggedInline<Item> = unsafe { TaggedInline::new(-100i16, 0) };
+        let copied = short;
+
+        assert_eq!(short.discriminant(), 0);
+        assert_eq!(unsafe { copied.as_inline::<i16>() }, -100);
+        assert_eq!(short.to_inner(), Item::Short(-100));
+        assert_eq!(short, copied);
+
+        let nothing: TaggedInline<Item> = unsafe { TaggedInline::dangling::<()>(2) };
+        assert_eq!(nothing.to_inner(), Item::Nothing);
+        assert!(short < nothing);
+
+        assert_eq!(
+            mem::size_of::<TaggedInline<Item>>(),
+            mem::size_of::<usize>()
+        );
+        assert_eq!(mem::size_of::<Option<Container>>(), mem::size_of::<usize>());
+    }
+
+    #[test]
+    fn tagged_box_round_trip() {
+        let flag = unsafe { TaggedInline::from_tagged_box(Item::Flag(true).into_tagged_box()) };
+        assert_eq!(flag.discriminant(), 1);
+        assert_eq!(
+            Item::from_tagged_box(flag.into_tagged_box()),
+            Item::Flag(true)
+        );
+
+        // The box it came from is still usable, as it never owned anything
+        assert_eq!(flag.to_inner(), Item::Flag(true));
+    }
+}
@@ -1,65 +1,347 @@
-use crate::discriminant::{
-    Discriminant, DISCRIMINANT_MASK, MAX_DISCRIMINANT, MAX_POINTER_VALUE, POINTER_WIDTH,
+use crate::{
+    discriminant::{Discriminant, DISCRIMINANT_BITS, LOW_DISCRIMINANT_BITS},
+    error::TagError,
+    provenance,
 };
-use core::fmt;
+use core::{fmt, marker::PhantomData, mem, ptr::NonNull};
 
 /// A pointer that holds a data pointer plus additional data stored as a [`Discriminant`]  
-/// Note: The discriminant must be <= [`MAX_DISCRIMINANT`], which is feature-dependent  
+/// Note: The discriminant must be <= [`MAX_DISCRIMINANT`], which depends on `FREE_BITS` and `LOW_BITS`
 ///
-/// [`MAX_DISCRIMINANT`]: crate::discriminant::MAX_DISCRIMINANT
+/// The discriminant can be stored in two places:
+///
+/// - `FREE_BITS` is the number of upper bits of the pointer that are used to store the discriminant.
+///   It defaults to [`DISCRIMINANT_BITS`], which is set by `TAGGED_BOX_RESERVED_WIDTH`
+/// - `LOW_BITS` is the number of lower bits of the pointer that are used to store the discriminant, which
+///   are always zero for pointers aligned to `2 ^ LOW_BITS` bytes. This doesn't rely on the address space
+///   of the target, so it's what 32bit targets use by default. It defaults to [`LOW_DISCRIMINANT_BITS`]
+///
+/// When both are used the discriminant is split between them, the lowest `LOW_BITS` bits of the discriminant
+/// are stored in the lower bits of the pointer and the rest in the upper bits. `FREE_BITS` plus `LOW_BITS`
+/// can be anywhere from 1 to 16
+///
+/// ```rust
+/// use tagged_box::TaggedPointer;
+///
+/// let integer = 10u32;
+///
+/// // `u32`s are aligned to 4 bytes, leaving 2 low bits free
+/// let tagged = TaggedPointer::<0, 2>::from_ptr(&integer, 3);
+///
+/// assert_eq!(tagged.discriminant(), 3);
+/// assert_eq!(tagged.as_ptr::<u32>(), &integer as *const u32);
+///
+/// # #[cfg(target_pointer_width = "64")]
+/// # {
+/// // Using both the upper and lower bits gives wider discriminants
+/// let hybrid = TaggedPointer::<4, 2>::from_ptr(&integer, 63);
+///
+/// assert_eq!(hybrid.discriminant(), 63);
+/// assert_eq!(hybrid.as_ptr::<u32>(), &integer as *const u32);
+/// # }
+/// ```
+///
+/// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+/// [`DISCRIMINANT_BITS`]: crate::discriminant::DISCRIMINANT_BITS
+/// [`LOW_DISCRIMINANT_BITS`]: crate::discriminant::LOW_DISCRIMINANT_BITS
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[repr(transparent)]
-pub struct TaggedPointer {
-    /// The tagged pointer, the upper bits are used to store arbitrary data
-    tagged_ptr: u64,
+pub struct TaggedPointer<
+    const FREE_BITS: u32 = DISCRIMINANT_BITS,
+    const LOW_BITS: u32 = LOW_DISCRIMINANT_BITS,
+> {
+    /// The tagged pointer, the upper or lower bits are used to store arbitrary data. It's kept as
+    /// a pointer and only modified through [`provenance`] so that it never loses its provenance,
+    /// and is never null so that `Option<TaggedPointer>` is the same size as `TaggedPointer`
+    ///
+    /// [`provenance`]: crate::provenance
+    tagged_ptr: NonNull<()>,
 }
 
-impl TaggedPointer {
+// Safety: A `TaggedPointer` is only an address and a discriminant, dereferencing it is already
+// unsafe and it's up to the owner of the pointed-to data to uphold thread safety
+unsafe impl<const FREE_BITS: u32, const LOW_BITS: u32> Send for TaggedPointer<FREE_BITS, LOW_BITS> {}
+unsafe impl<const FREE_BITS: u32, const LOW_BITS: u32> Sync for TaggedPointer<FREE_BITS, LOW_BITS> {}
+
+impl<const FREE_BITS: u32, const LOW_BITS: u32> TaggedPointer<FREE_BITS, LOW_BITS> {
+    /// The reserved width of a pointer, the number of bits that are left for addresses
+    pub const POINTER_WIDTH: u32 = usize::BITS - FREE_BITS;
+
+    /// The maximum allowed value of a discriminant, `2 ^ (FREE_BITS + LOW_BITS) - 1`
+    #[allow(clippy::cast_possible_truncation)]
+    pub const MAX_DISCRIMINANT: Discriminant =
+        ((1u32 << (FREE_BITS + LOW_BITS)) - 1) as Discriminant;
+
+    /// The maximum allowed value of a pointer, `2 ^ POINTER_WIDTH - 1`
+    pub const MAX_POINTER_VALUE: u64 = usize::MAX as u64 >> FREE_BITS;
+
+    /// The alignment that pointers must have for their lower `LOW_BITS` bits to be free
+    pub const MIN_ALIGNMENT: usize = 1 << LOW_BITS;
+
+    /// A mask of the lower free bits of a tagged pointer
+    pub const LOW_MASK: u64 = (1 << LOW_BITS) - 1;
+
+    /// A mask to remove the upper and lower free bits of a tagged pointer
+    pub const DISCRIMINANT_MASK: u64 = Self::MAX_POINTER_VALUE & !Self::LOW_MASK;
+
+    /// Rejects any `FREE_BITS` and `LOW_BITS` that can't be represented at compile time
+    const VALID_WIDTH: () = assert!(
+        FREE_BITS + LOW_BITS >= 1 && FREE_BITS + LOW_BITS <= Discriminant::BITS,
+        "`FREE_BITS` plus `LOW_BITS` must be between 1 and 16",
+    );
+
     /// Create a new tagged pointer from a pointer and a discriminant
     ///
     /// # Panics
     ///
-    /// Panics if `discriminant` is greater than [`MAX_DISCRIMINANT`] or if
-    /// `ptr` is greater than [`MAX_POINTER_VALUE`]
+    /// Panics if both `ptr` and `discriminant` are zero, as the tagged pointer would be null.
+    ///
+    /// In debug builds, panics if `discriminant` is greater than [`MAX_DISCRIMINANT`], if `ptr` is greater
+    /// than [`MAX_POINTER_VALUE`] or if `ptr` isn't aligned to [`MIN_ALIGNMENT`]. In release builds these
+    /// aren't checked, use [`TaggedPointer::try_new`] for values that can't be trusted
     ///
-    /// [`MAX_DISCRIMINANT`]: crate::discriminant::MAX_DISCRIMINANT
-    /// [`MAX_POINTER_VALUE`]: crate::discriminant::MAX_POINTER_VALUE
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    /// [`MAX_POINTER_VALUE`]: crate::TaggedPointer::MAX_POINTER_VALUE
+    /// [`MIN_ALIGNMENT`]: crate::TaggedPointer::MIN_ALIGNMENT
+    /// [`TaggedPointer::try_new`]: crate::TaggedPointer#try_new
     #[inline]
-    #[allow(clippy::absurd_extreme_comparisons)]
     pub fn new(ptr: u64, discriminant: Discriminant) -> Self {
+        Self::debug_check(ptr, discriminant);
+
+        // The pointer was given to us as an integer, so the best we can do is to pick up
+        // whatever provenance was exposed for its address
+        Self::tag(
+            provenance::with_exposed_provenance(ptr as usize),
+            discriminant,
+        )
+    }
+
+    /// Create a new tagged pointer from a pointer and a discriminant, keeping the pointer's provenance
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics under the same conditions as [`TaggedPointer::new`]
+    ///
+    /// [`TaggedPointer::new`]: crate::TaggedPointer#new
+    #[inline]
+    pub(crate) fn from_mut_ptr(ptr: *mut (), discriminant: Discriminant) -> Self {
+        Self::debug_check(provenance::addr(ptr) as u64, discriminant);
+
+        Self::tag(ptr, discriminant)
+    }
+
+    /// Stores `discriminant` into `ptr`, only checking that the tagged pointer isn't null
+    #[inline]
+    fn tag(ptr: *mut (), discriminant: Discriminant) -> Self {
+        let tagged_ptr = provenance::map_addr(ptr, |addr| {
+            Self::store_discriminant(addr as u64, discriminant) as usize
+        });
+
+        Self {
+            tagged_ptr: NonNull::new(tagged_ptr)
+                .expect("Attempted to create a null tagged pointer"),
+        }
+    }
+
+    /// Checks that `ptr` and `discriminant` can be stored in debug builds
+    #[inline]
+    #[allow(clippy::absurd_extreme_comparisons)]
+    fn debug_check(ptr: u64, discriminant: Discriminant) {
         debug_assert!(
-            discriminant <= MAX_DISCRIMINANT,
+            discriminant <= Self::MAX_DISCRIMINANT,
             "Attempted to store a discriminant of {} while the max value is {}",
             discriminant,
-            MAX_DISCRIMINANT,
+            Self::MAX_DISCRIMINANT,
         );
         debug_assert!(
-            ptr <= MAX_POINTER_VALUE,
+            ptr <= Self::MAX_POINTER_VALUE,
             "If you are receiving this error, then your hardware uses more than {} bits of a pointer to store addresses. \
-            It is recommended that you use a different feature for the `tagged-box` crate using `features = [\"{}bits\"]` or above.
+            It is recommended that you use {} or less free bits, either through `FREE_BITS` or `TAGGED_BOX_RESERVED_WIDTH=\"{}bits\"`.
             ",
-            POINTER_WIDTH,
-            POINTER_WIDTH + 1,
+            Self::POINTER_WIDTH,
+            FREE_BITS.saturating_sub(1),
+            Self::POINTER_WIDTH + 1,
+        );
+        debug_assert!(
+            ptr & Self::LOW_MASK == 0,
+            "Attempted to store a pointer aligned to less than {} bytes while {} low bits are used",
+            Self::MIN_ALIGNMENT,
+            LOW_BITS,
         );
+    }
+
+    /// Create a new tagged pointer from a pointer and a discriminant, returning an error if the
+    /// discriminant or pointer can't be stored
+    ///
+    /// ```rust
+    /// use tagged_box::{TagError, TaggedPointer};
+    ///
+    /// let integer = 10u32;
+    /// let pointer = &integer as *const u32 as u64;
+    ///
+    /// let tagged = TaggedPointer::<0, 2>::try_new(pointer, 3).unwrap();
+    /// assert_eq!(tagged.discriminant(), 3);
+    ///
+    /// assert_eq!(
+    ///     TaggedPointer::<0, 2>::try_new(pointer, 4),
+    ///     Err(TagError::DiscriminantOutOfRange { discriminant: 4, max: 3 }),
+    /// );
+    /// assert_eq!(
+    ///     TaggedPointer::<0, 2>::try_new(pointer + 1, 3),
+    ///     Err(TagError::Misaligned { pointer: pointer + 1, align: 4 }),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TagError::DiscriminantOutOfRange`] if `discriminant` is greater than [`MAX_DISCRIMINANT`],
+    /// [`TagError::PointerTooWide`] if `ptr` is greater than [`MAX_POINTER_VALUE`], [`TagError::Misaligned`]
+    /// if `ptr` isn't aligned to [`MIN_ALIGNMENT`] and [`TagError::Null`] if both `ptr` and `discriminant` are zero
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    /// [`MAX_POINTER_VALUE`]: crate::TaggedPointer::MAX_POINTER_VALUE
+    /// [`MIN_ALIGNMENT`]: crate::TaggedPointer::MIN_ALIGNMENT
+    /// [`TagError::DiscriminantOutOfRange`]: crate::TagError::DiscriminantOutOfRange
+    /// [`TagError::PointerTooWide`]: crate::TagError::PointerTooWide
+    /// [`TagError::Misaligned`]: crate::TagError::Misaligned
+    /// [`TagError::Null`]: crate::TagError::Null
+    #[inline]
+    pub fn try_new(ptr: u64, discriminant: Discriminant) -> Result<Self, TagError> {
+        Self::check(ptr, discriminant)?;
+
+        Ok(Self::tag(
+            provenance::with_exposed_provenance(ptr as usize),
+            discriminant,
+        ))
+    }
+
+    /// Create a new tagged pointer from a pointer and a discriminant, keeping the pointer's provenance
+    /// and returning an error if the discriminant or pointer can't be stored, see [`TaggedPointer::try_new`]
+    ///
+    /// [`TaggedPointer::try_new`]: crate::TaggedPointer#try_new
+    #[inline]
+    pub(crate) fn try_from_mut_ptr(
+        ptr: *mut (),
+        discriminant: Discriminant,
+    ) -> Result<Self, TagError> {
+        Self::check(provenance::addr(ptr) as u64, discriminant)?;
+
+        Ok(Self::tag(ptr, discriminant))
+    }
 
-        let tagged_ptr = Self::store_discriminant(ptr, discriminant);
+    /// Checks that `ptr` and `discriminant` can be stored
+    #[inline]
+    #[allow(clippy::absurd_extreme_comparisons)]
+    fn check(ptr: u64, discriminant: Discriminant) -> Result<(), TagError> {
+        if discriminant > Self::MAX_DISCRIMINANT {
+            return Err(TagError::DiscriminantOutOfRange {
+                discriminant,
+                max: Self::MAX_DISCRIMINANT,
+            });
+        }
+
+        if ptr > Self::MAX_POINTER_VALUE {
+            return Err(TagError::PointerTooWide {
+                pointer: ptr,
+                max: Self::MAX_POINTER_VALUE,
+            });
+        }
+
+        if ptr & Self::LOW_MASK != 0 {
+            return Err(TagError::Misaligned {
+                pointer: ptr,
+                align: Self::MIN_ALIGNMENT,
+            });
+        }
+
+        if ptr == 0 && discriminant == 0 {
+            return Err(TagError::Null);
+        }
 
-        Self { tagged_ptr }
+        Ok(())
     }
 
+    /// Create a new tagged pointer from a typed pointer and a discriminant, keeping the pointer's
+    /// provenance and checking at compile time that `U` is aligned enough to leave `LOW_BITS` bits free
+    ///
+    /// ```compile_fail
+    /// # use tagged_box::TaggedPointer;
+    /// let byte = 10u8;
+    ///
+    /// // `u8`s are only aligned to 1 byte, so there's no low bits to spare
+    /// let tagged = TaggedPointer::<0, 3>::from_ptr(&byte, 7);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if both `ptr` and `discriminant` are zero, as the tagged pointer would be null.
+    ///
+    /// In debug builds, panics if `discriminant` is greater than [`MAX_DISCRIMINANT`], if `ptr` is
+    /// greater than [`MAX_POINTER_VALUE`] or if `ptr` isn't aligned to [`MIN_ALIGNMENT`]. In release
+    /// builds these aren't checked, use [`TaggedPointer::try_new`] for values that can't be trusted
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    /// [`MAX_POINTER_VALUE`]: crate::TaggedPointer::MAX_POINTER_VALUE
+    /// [`MIN_ALIGNMENT`]: crate::TaggedPointer::MIN_ALIGNMENT
+    /// [`TaggedPointer::try_new`]: crate::TaggedPointer#try_new
+    #[inline]
+    pub fn from_ptr<U>(ptr: *const U, discriminant: Discriminant) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = AlignmentCheck::<U, LOW_BITS>::FITS;
+
+        Self::from_mut_ptr(ptr as *mut (), discriminant)
+    }
+
+    /// Creates a dangling tagged pointer that is aligned for `T` and has its lower `LOW_BITS`
+    /// bits free, see [`NonNull::dangling`] for more information
+    ///
+    /// [`NonNull::dangling`]: https://doc.rust-lang.org/core/ptr/struct.NonNull.html#method.dangling
     #[inline]
     pub const fn dangling<T: Sized>(discriminant: Discriminant) -> Self {
+        let align = if mem::align_of::<T>() > Self::MIN_ALIGNMENT {
+            mem::align_of::<T>()
+        } else {
+            Self::MIN_ALIGNMENT
+        };
+
+        let tagged_ptr = provenance::without_provenance(Self::store_discriminant(
+            align as u64,
+            discriminant,
+        ) as usize);
+
+        // Safety: Alignments are never zero, so the tagged pointer can't be null
+        Self {
+            tagged_ptr: unsafe { NonNull::new_unchecked(tagged_ptr) },
+        }
+    }
+
+    /// Creates a tagged pointer from a raw tagged pointer previously returned by [`TaggedPointer::as_raw_ptr`]
+    ///
+    /// # Safety
+    ///
+    /// `tagged_ptr` must have come from [`TaggedPointer::as_raw_ptr`] of a tagged pointer
+    /// with the same `FREE_BITS` and `LOW_BITS`
+    ///
+    /// [`TaggedPointer::as_raw_ptr`]: crate::TaggedPointer#as_raw_ptr
+    #[inline]
+    pub(crate) const unsafe fn from_raw_ptr(tagged_ptr: *mut ()) -> Self {
         Self {
-            tagged_ptr: Self::store_discriminant(core::mem::align_of::<T>() as u64, discriminant),
+            tagged_ptr: NonNull::new_unchecked(tagged_ptr),
         }
     }
 
+    /// Returns the raw tagged pointer with its provenance, without removing the discriminant
+    #[inline]
+    pub(crate) const fn as_raw_ptr(self) -> *mut () {
+        self.tagged_ptr.as_ptr()
+    }
+
     /// Fetches the [`Discriminant`] of a tagged pointer
     ///
     /// [`Discriminant`]: crate::Discriminant
     #[inline]
-    pub const fn discriminant(self) -> Discriminant {
-        Self::fetch_discriminant(self.tagged_ptr)
+    pub fn discriminant(self) -> Discriminant {
+        Self::fetch_discriminant(self.as_raw_u64())
     }
 
     /// Gains a reference to the inner value of the pointer
@@ -72,7 +354,7 @@ impl TaggedPointer {
     #[inline]
     #[allow(clippy::should_implement_trait)]
     pub unsafe fn as_ref<T>(&self) -> &T {
-        &*(Self::strip_discriminant(self.tagged_ptr) as *const T)
+        &*self.as_ptr::<T>()
     }
 
     /// Gains a mutable reference to the inner value of the pointer
@@ -84,13 +366,13 @@ impl TaggedPointer {
     /// [`TaggedPointer::new`]: crate::TaggedPointer#new
     #[inline]
     pub unsafe fn as_mut_ref<T>(&mut self) -> &mut T {
-        &mut *(Self::strip_discriminant(self.tagged_ptr) as *mut T)
+        &mut *self.as_mut_ptr::<T>()
     }
 
     /// Returns the pointer as a u64, removing the discriminant
     #[inline]
-    pub const fn as_u64(self) -> u64 {
-        Self::strip_discriminant(self.tagged_ptr)
+    pub fn as_u64(self) -> u64 {
+        Self::strip_discriminant(self.as_raw_u64())
     }
 
     /// Returns the raw tagged pointer, without removing the discriminant
@@ -100,35 +382,158 @@ impl TaggedPointer {
     /// Attempting to dereference this u64 will not point to valid memory!
     ///
     #[inline]
-    pub const fn as_raw_u64(self) -> u64 {
-        self.tagged_ptr
+    pub fn as_raw_u64(self) -> u64 {
+        provenance::addr(self.tagged_ptr.as_ptr()) as u64
     }
 
     /// Converts a tagged pointer into a raw pointer, removing the discriminant
     #[inline]
-    pub const fn as_ptr<T>(self) -> *const T {
-        Self::strip_discriminant(self.tagged_ptr) as *const T
+    pub fn as_ptr<T>(self) -> *const T {
+        self.as_mut_ptr::<T>() as *const T
     }
 
     /// Converts a tagged pointer into a raw pointer, removing the discriminant
     #[inline]
     pub fn as_mut_ptr<T>(self) -> *mut T {
-        Self::strip_discriminant(self.tagged_ptr) as *mut T
+        let ptr = provenance::map_addr(self.tagged_ptr.as_ptr(), |addr| {
+            Self::strip_discriminant(addr as u64) as usize
+        });
+
+        ptr as *mut T
+    }
+
+    /// Returns a copy of the tagged pointer with its discriminant replaced by `discriminant`,
+    /// keeping the same pointer
+    ///
+    /// ```rust
+    /// use tagged_box::TaggedPointer;
+    ///
+    /// let integer = 10u32;
+    /// let tagged = TaggedPointer::<0, 2>::from_ptr(&integer, 1);
+    /// let retagged = tagged.with_discriminant(3);
+    ///
+    /// assert_eq!(retagged.discriminant(), 3);
+    /// assert_eq!(retagged.as_ptr::<u32>(), tagged.as_ptr::<u32>());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting tagged pointer would be null, and in debug builds if `discriminant`
+    /// is greater than [`MAX_DISCRIMINANT`]
+    ///
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    #[inline]
+    pub fn with_discriminant(self, discriminant: Discriminant) -> Self {
+        let ptr = self.as_mut_ptr::<()>();
+        Self::debug_check(provenance::addr(ptr) as u64, discriminant);
+
+        Self::tag(ptr, discriminant)
+    }
+
+    /// Replaces the discriminant of the tagged pointer in place, see [`TaggedPointer::with_discriminant`]
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`TaggedPointer::with_discriminant`]
+    ///
+    /// [`TaggedPointer::with_discriminant`]: crate::TaggedPointer#with_discriminant
+    #[inline]
+    pub fn set_discriminant(&mut self, discriminant: Discriminant) {
+        *self = self.with_discriminant(discriminant);
+    }
+
+    /// Returns a copy of the tagged pointer with its discriminant replaced by the result of `map`,
+    /// keeping the same pointer
+    ///
+    /// ```rust
+    /// use tagged_box::TaggedPointer;
+    ///
+    /// let integer = 10u32;
+    /// let tagged = TaggedPointer::<0, 2>::from_ptr(&integer, 1);
+    ///
+    /// assert_eq!(tagged.map_discriminant(|d| d + 1).discriminant(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`TaggedPointer::with_discriminant`]
+    ///
+    /// [`TaggedPointer::with_discriminant`]: crate::TaggedPointer#with_discriminant
+    #[inline]
+    pub fn map_discriminant(self, map: impl FnOnce(Discriminant) -> Discriminant) -> Self {
+        self.with_discriminant(map(self.discriminant()))
+    }
+
+    /// Returns a new tagged pointer to `ptr`, keeping the current discriminant
+    ///
+    /// ```rust
+    /// use tagged_box::TaggedPointer;
+    ///
+    /// let (first, second) = (10u32, 20u32);
+    /// let tagged = TaggedPointer::<0, 2>::from_ptr(&first, 3);
+    /// let moved = tagged.with_ptr(&second);
+    ///
+    /// assert_eq!(moved.discriminant(), 3);
+    /// assert_eq!(unsafe { *moved.as_ref::<u32>() }, 20);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`TaggedPointer::from_ptr`]
+    ///
+    /// [`TaggedPointer::from_ptr`]: crate::TaggedPointer#from_ptr
+    #[inline]
+    pub fn with_ptr<U>(self, ptr: *const U) -> Self {
+        Self::from_ptr(ptr, self.discriminant())
+    }
+
+    /// Returns a new tagged pointer to the result of `map`, keeping the current discriminant
+    ///
+    /// ```rust
+    /// use tagged_box::TaggedPointer;
+    ///
+    /// let integers = [10u32, 20, 30];
+    /// let tagged = TaggedPointer::<0, 2>::from_ptr(integers.as_ptr(), 2);
+    /// let next = tagged.map_ptr(|ptr: *const u32| ptr.wrapping_add(1));
+    ///
+    /// assert_eq!(next.discriminant(), 2);
+    /// assert_eq!(unsafe { *next.as_ref::<u32>() }, 20);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`TaggedPointer::from_ptr`]
+    ///
+    /// [`TaggedPointer::from_ptr`]: crate::TaggedPointer#from_ptr
+    #[inline]
+    pub fn map_ptr<U>(self, map: impl FnOnce(*const U) -> *const U) -> Self {
+        self.with_ptr(map(self.as_ptr::<U>()))
     }
 
     /// Store a [`Discriminant`] into a tagged pointer without any checks
     ///
     /// # Safety
     ///
-    /// `discriminant` must be <= [`MAX_DISCRIMINANT`] and `pointer` must be <=
-    /// [`MAX_POINTER_VALUE`]
+    /// `discriminant` must be <= [`MAX_DISCRIMINANT`], `pointer` must be <=
+    /// [`MAX_POINTER_VALUE`] and `pointer` must be aligned to [`MIN_ALIGNMENT`]
     ///
     /// [`Discriminant`]: crate::Discriminant
-    /// [`MAX_DISCRIMINANT`]: crate::discriminant::MAX_DISCRIMINANT
-    /// [`MAX_POINTER_VALUE`]: crate::discriminant::MAX_POINTER_VALUE
+    /// [`MAX_DISCRIMINANT`]: crate::TaggedPointer::MAX_DISCRIMINANT
+    /// [`MAX_POINTER_VALUE`]: crate::TaggedPointer::MAX_POINTER_VALUE
+    /// [`MIN_ALIGNMENT`]: crate::TaggedPointer::MIN_ALIGNMENT
     #[inline]
     pub const fn store_discriminant(pointer: u64, discriminant: Discriminant) -> u64 {
-        pointer | ((discriminant as u64) << POINTER_WIDTH)
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_WIDTH;
+
+        let low = discriminant as u64 & Self::LOW_MASK;
+        let high = if FREE_BITS == 0 {
+            0
+        } else {
+            ((discriminant as u64) >> LOW_BITS) << Self::POINTER_WIDTH
+        };
+
+        pointer | low | high
     }
 
     /// Fetch a [`Discriminant`] from a tagged pointer    
@@ -136,7 +541,14 @@ impl TaggedPointer {
     /// [`Discriminant`]: crate::Discriminant
     #[allow(clippy::cast_possible_truncation)]
     pub const fn fetch_discriminant(pointer: u64) -> Discriminant {
-        (pointer >> POINTER_WIDTH) as Discriminant
+        let low = pointer & Self::LOW_MASK;
+        let high = if FREE_BITS == 0 {
+            0
+        } else {
+            (pointer >> Self::POINTER_WIDTH) << LOW_BITS
+        };
+
+        (low | high) as Discriminant
     }
 
     /// Strip the [`Discriminant`] from a tagged pointer, returning only the valid pointer as a usize
@@ -144,53 +556,76 @@ impl TaggedPointer {
     /// [`Discriminant`]: crate::Discriminant
     #[inline]
     pub const fn strip_discriminant(pointer: u64) -> u64 {
-        pointer & DISCRIMINANT_MASK
+        pointer & Self::DISCRIMINANT_MASK
     }
 }
 
-impl fmt::Debug for TaggedPointer {
+impl<const FREE_BITS: u32, const LOW_BITS: u32> fmt::Debug for TaggedPointer<FREE_BITS, LOW_BITS> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TaggedPointer")
-            .field("raw", &(self.as_raw_u64() as *const ()))
+            .field("raw", &self.tagged_ptr)
             .field("ptr", &self.as_ptr::<()>())
             .field("discriminant", &self.discriminant())
             .finish()
     }
 }
 
-impl fmt::Pointer for TaggedPointer {
+impl<const FREE_BITS: u32, const LOW_BITS: u32> fmt::Pointer
+    for TaggedPointer<FREE_BITS, LOW_BITS>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Pointer::fmt(&self.as_ptr::<()>(), f)
     }
 }
 
-impl_fmt!(TaggedPointer => LowerHex, UpperHex, Binary, Octal);
+impl_fmt!(impl[const FREE_BITS: u32, const LOW_BITS: u32] TaggedPointer<FREE_BITS, LOW_BITS> => LowerHex, UpperHex, Binary, Octal);
+
+/// Checks at compile time that pointers to `U` have at least `LOW_BITS` free lower bits
+struct AlignmentCheck<U, const LOW_BITS: u32>(PhantomData<U>);
+
+impl<U, const LOW_BITS: u32> AlignmentCheck<U, LOW_BITS> {
+    const FITS: () = assert!(
+        LOW_BITS <= mem::align_of::<U>().trailing_zeros(),
+        "The alignment of the pointed-to type doesn't leave `LOW_BITS` bits free",
+    );
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::discriminant;
+    use crate::discriminant::{self, DISCRIMINANT_BITS};
     use alloc::string::String;
     use core::slice;
 
     #[test]
+    #[cfg(target_pointer_width = "64")]
     fn utility_functions() {
         let ptr = 0xF00D_BEEF;
         let discrim = discriminant::MAX_DISCRIMINANT / 2;
-        let stored = TaggedPointer::new(ptr, discrim).as_raw_u64();
+        let stored = TaggedPointer::<DISCRIMINANT_BITS>::new(ptr, discrim).as_raw_u64();
 
-        assert_eq!(TaggedPointer::strip_discriminant(stored), ptr);
-        assert_eq!(TaggedPointer::fetch_discriminant(stored), discrim);
-        assert_eq!(TaggedPointer::store_discriminant(ptr, discrim), stored);
+        assert_eq!(
+            TaggedPointer::<DISCRIMINANT_BITS>::strip_discriminant(stored),
+            ptr
+        );
+        assert_eq!(
+            TaggedPointer::<DISCRIMINANT_BITS>::fetch_discriminant(stored),
+            discrim
+        );
+        assert_eq!(
+            TaggedPointer::<DISCRIMINANT_BITS>::store_discriminant(ptr, discrim),
+            stored
+        );
     }
 
     #[test]
+    #[cfg(target_pointer_width = "64")]
     fn tagged_pointer() {
         let integer = 100i32;
         let int_ptr = &integer as *const _ as u64;
         let discriminant = 10;
 
-        let ptr = TaggedPointer::new(int_ptr, discriminant);
+        let ptr: TaggedPointer = TaggedPointer::new(int_ptr, discriminant);
 
         assert_eq!(ptr.discriminant(), discriminant);
         assert_eq!(ptr.as_u64(), int_ptr);
@@ -201,11 +636,12 @@ mod tests {
     }
 
     #[test]
+    #[cfg(target_pointer_width = "64")]
     fn max_pointer() {
         let ptr = discriminant::MAX_POINTER_VALUE;
         let discriminant = discriminant::MAX_DISCRIMINANT;
 
-        let tagged = TaggedPointer::new(ptr, discriminant);
+        let tagged: TaggedPointer = TaggedPointer::new(ptr, discriminant);
 
         assert_eq!(tagged.discriminant(), discriminant);
         assert_eq!(tagged.as_u64(), ptr);
@@ -216,19 +652,20 @@ mod tests {
         let ptr: u64 = 0;
         let discriminant = discriminant::MAX_DISCRIMINANT;
 
-        let tagged = TaggedPointer::new(ptr, discriminant);
+        let tagged: TaggedPointer = TaggedPointer::new(ptr, discriminant);
 
         assert_eq!(tagged.discriminant(), discriminant);
         assert_eq!(tagged.as_u64(), ptr);
     }
 
     #[test]
+    #[cfg(target_pointer_width = "64")]
     fn max_discriminant() {
         let integer = 100usize;
         let int_ptr = &integer as *const _ as u64;
         let discriminant = discriminant::MAX_DISCRIMINANT;
 
-        let ptr = TaggedPointer::new(int_ptr, discriminant);
+        let ptr: TaggedPointer = TaggedPointer::new(int_ptr, discriminant);
 
         assert_eq!(ptr.discriminant(), discriminant);
         assert_eq!(ptr.as_u64(), int_ptr);
@@ -239,12 +676,13 @@ mod tests {
     }
 
     #[test]
+    #[cfg(target_pointer_width = "64")]
     fn min_discriminant() {
         let integer = 100usize;
         let int_ptr = &integer as *const _ as u64;
         let discriminant = 0;
 
-        let ptr = TaggedPointer::new(int_ptr, discriminant);
+        let ptr: TaggedPointer = TaggedPointer::new(int_ptr, discriminant);
 
         assert_eq!(ptr.discriminant(), discriminant);
         assert_eq!(ptr.as_u64(), int_ptr);
@@ -255,12 +693,13 @@ mod tests {
     }
 
     #[test]
+    #[cfg(target_pointer_width = "64")]
     fn string_pointer() {
         let string = String::from("Hello world!");
         let str_ptr = string.as_ptr() as u64;
         let discriminant = discriminant::MAX_DISCRIMINANT;
 
-        let ptr = TaggedPointer::new(str_ptr, discriminant);
+        let ptr: TaggedPointer = TaggedPointer::new(str_ptr, discriminant);
 
         assert_eq!(ptr.discriminant(), discriminant);
         assert_eq!(ptr.as_u64(), str_ptr);
@@ -282,6 +721,238 @@ mod tests {
             panic!("Adding one to discriminant would overflow type, aborting test");
         };
 
-        TaggedPointer::new(pointer, discriminant);
+        TaggedPointer::<DISCRIMINANT_BITS>::new(pointer, discriminant);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn free_bit_widths() {
+        let integer = 100usize;
+        let int_ptr = &integer as *const _ as u64;
+
+        let narrow = TaggedPointer::<1>::new(int_ptr, TaggedPointer::<1>::MAX_DISCRIMINANT);
+        assert_eq!(narrow.discriminant(), 1);
+        assert_eq!(narrow.as_u64(), int_ptr);
+
+        let byte = TaggedPointer::<8>::new(int_ptr, TaggedPointer::<8>::MAX_DISCRIMINANT);
+        assert_eq!(byte.discriminant(), 255);
+        assert_eq!(byte.as_u64(), int_ptr);
+
+        let wide = TaggedPointer::<16>::new(int_ptr, TaggedPointer::<16>::MAX_DISCRIMINANT);
+        assert_eq!(wide.discriminant(), 65535);
+        assert_eq!(wide.as_u64(), int_ptr);
+
+        unsafe {
+            assert_eq!(narrow.as_ref::<usize>(), &integer);
+            assert_eq!(byte.as_ref::<usize>(), &integer);
+            assert_eq!(wide.as_ref::<usize>(), &integer);
+        }
+
+        assert_eq!(TaggedPointer::<8>::POINTER_WIDTH, 56);
+        assert_eq!(TaggedPointer::<8>::MAX_POINTER_VALUE, (1 << 56) - 1);
+        assert_eq!(
+            TaggedPointer::<DISCRIMINANT_BITS>::MAX_DISCRIMINANT,
+            discriminant::MAX_DISCRIMINANT
+        );
+    }
+
+    #[test]
+    fn low_bits() {
+        // `u64`s are only aligned to 4 bytes on some 32bit targets
+        #[derive(Debug, PartialEq)]
+        #[repr(align(8))]
+        struct Aligned(u64);
+
+        let integer = Aligned(100);
+        let int_ptr = &integer as *const _ as u64;
+
+        let tagged =
+            TaggedPointer::<0, 3>::from_ptr(&integer, TaggedPointer::<0, 3>::MAX_DISCRIMINANT);
+        assert_eq!(tagged.discriminant(), 7);
+        assert_eq!(tagged.as_u64(), int_ptr);
+        assert_eq!(tagged.as_raw_u64(), int_ptr | 7);
+
+        unsafe {
+            assert_eq!(tagged.as_ref::<Aligned>(), &integer);
+        }
+
+        let stored = TaggedPointer::<0, 3>::store_discriminant(int_ptr, 5);
+        assert_eq!(TaggedPointer::<0, 3>::fetch_discriminant(stored), 5);
+        assert_eq!(TaggedPointer::<0, 3>::strip_discriminant(stored), int_ptr);
+
+        let dangling = TaggedPointer::<0, 4>::dangling::<u8>(15);
+        assert_eq!(dangling.discriminant(), 15);
+        assert_eq!(dangling.as_u64(), 16);
+
+        assert_eq!(TaggedPointer::<0, 3>::POINTER_WIDTH, usize::BITS);
+        assert_eq!(TaggedPointer::<0, 3>::MIN_ALIGNMENT, 8);
+        assert_eq!(TaggedPointer::<0, 3>::LOW_MASK, 0b111);
+        assert_eq!(
+            TaggedPointer::<0, 3>::DISCRIMINANT_MASK,
+            usize::MAX as u64 & !0b111
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(miri, ignore)]
+    fn misaligned_low_bits() {
+        TaggedPointer::<0, 3>::new(0xF00D_BEE1, 0);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn hybrid_bits() {
+        let integer = 100u64;
+        let int_ptr = &integer as *const _ as u64;
+
+        assert_eq!(TaggedPointer::<4, 3>::MAX_DISCRIMINANT, 127);
+        assert_eq!(TaggedPointer::<4, 3>::POINTER_WIDTH, 60);
+        assert_eq!(
+            TaggedPointer::<4, 3>::DISCRIMINANT_MASK,
+            (u64::MAX >> 4) & !0b111
+        );
+
+        for discriminant in 0..=TaggedPointer::<4, 3>::MAX_DISCRIMINANT {
+            let tagged = TaggedPointer::<4, 3>::from_ptr(&integer, discriminant);
+
+            assert_eq!(tagged.discriminant(), discriminant);
+            assert_eq!(tagged.as_u64(), int_ptr);
+            unsafe {
+                assert_eq!(tagged.as_ref::<u64>(), &integer);
+            }
+        }
+
+        // The lowest bits of the discriminant go into the lower bits of the pointer
+        let stored = TaggedPointer::<4, 3>::store_discriminant(int_ptr, 0b101_1010);
+        assert_eq!(stored & 0b111, 0b010);
+        assert_eq!(stored >> 60, 0b1011);
+        assert_eq!(TaggedPointer::<4, 3>::strip_discriminant(stored), int_ptr);
+
+        let wide = TaggedPointer::<12, 4>::dangling::<u8>(Discriminant::MAX);
+        assert_eq!(wide.discriminant(), Discriminant::MAX);
+        assert_eq!(wide.as_u64(), 16);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn pointer_width_32() {
+        // Every bit of a pointer is used for addresses, so the default is to use the lower bits
+        assert_eq!(DISCRIMINANT_BITS, 0);
+        assert_eq!(discriminant::LOW_DISCRIMINANT_BITS, 4);
+        assert_eq!(discriminant::MAX_DISCRIMINANT, 15);
+        assert_eq!(TaggedPointer::<0, 4>::POINTER_WIDTH, 32);
+        assert_eq!(TaggedPointer::<0, 4>::MAX_POINTER_VALUE, u32::MAX as u64);
+        assert_eq!(
+            <TaggedPointer>::MAX_DISCRIMINANT,
+            discriminant::MAX_DISCRIMINANT
+        );
+        assert_eq!(
+            <TaggedPointer>::DISCRIMINANT_MASK,
+            discriminant::DISCRIMINANT_MASK
+        );
+
+        let integer = 100u64;
+        let int_ptr = &integer as *const u64;
+
+        // Nothing on the stack is guaranteed to be aligned to 16 bytes, so use a dangling
+        // pointer for the default number of lower bits
+        let aligned = <TaggedPointer>::dangling::<u64>(discriminant::MAX_DISCRIMINANT);
+        assert_eq!(aligned.discriminant(), discriminant::MAX_DISCRIMINANT);
+        assert_eq!(aligned.as_u64(), 16);
+
+        let tagged = TaggedPointer::<0, 2>::from_ptr(int_ptr, 3);
+        assert_eq!(tagged.discriminant(), 3);
+        assert_eq!(tagged.as_ptr::<u64>(), int_ptr);
+        unsafe {
+            assert_eq!(tagged.as_ref::<u64>(), &integer);
+        }
+
+        let high = TaggedPointer::<0, 4>::MAX_POINTER_VALUE & !0b1111;
+        let tagged: TaggedPointer = TaggedPointer::new(high, 5);
+        assert_eq!(tagged.discriminant(), 5);
+        assert_eq!(tagged.as_u64(), high);
+    }
+
+    #[test]
+    fn try_new() {
+        let integer = 100u32;
+        let int_ptr = &integer as *const _ as u64;
+
+        let tagged = TaggedPointer::<0, 2>::try_new(int_ptr, 3).unwrap();
+        assert_eq!(tagged.discriminant(), 3);
+        assert_eq!(tagged.as_u64(), int_ptr);
+
+        assert_eq!(
+            TaggedPointer::<0, 2>::try_new(int_ptr, 4),
+            Err(TagError::DiscriminantOutOfRange {
+                discriminant: 4,
+                max: 3
+            }),
+        );
+        assert_eq!(
+            TaggedPointer::<0, 2>::try_new(int_ptr | 0b10, 0),
+            Err(TagError::Misaligned {
+                pointer: int_ptr | 0b10,
+                align: 4
+            }),
+        );
+        assert_eq!(
+            TaggedPointer::<8, 0>::try_new(u64::MAX, 0),
+            Err(TagError::PointerTooWide {
+                pointer: u64::MAX,
+                max: TaggedPointer::<8, 0>::MAX_POINTER_VALUE,
+            }),
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn tag_mutation() {
+        let integers = [10u32, 20, 30];
+        let mut tagged = TaggedPointer::<1, 2>::from_ptr(integers.as_ptr(), 0b101);
+
+        let retagged = tagged.with_discriminant(0b010);
+        assert_eq!(retagged.discriminant(), 0b010);
+        assert_eq!(retagged.as_ptr::<u32>(), integers.as_ptr());
+
+        tagged.set_discriminant(0b111);
+        assert_eq!(tagged.discriminant(), 0b111);
+        assert_eq!(tagged.as_ptr::<u32>(), integers.as_ptr());
+
+        let mapped = tagged.map_discriminant(|discriminant| discriminant & 0b011);
+        assert_eq!(mapped.discriminant(), 0b011);
+
+        let moved = mapped.with_ptr(&integers[2]);
+        assert_eq!(moved.discriminant(), 0b011);
+        unsafe {
+            assert_eq!(moved.as_ref::<u32>(), &30);
+        }
+
+        let previous = moved.map_ptr(|ptr: *const u32| ptr.wrapping_sub(1));
+        assert_eq!(previous.discriminant(), 0b011);
+        unsafe {
+            assert_eq!(previous.as_ref::<u32>(), &20);
+        }
+    }
+
+    #[test]
+    fn niche() {
+        assert_eq!(
+            mem::size_of::<Option<TaggedPointer>>(),
+            mem::size_of::<TaggedPointer>()
+        );
+        assert_eq!(mem::size_of::<TaggedPointer>(), mem::size_of::<usize>());
+        assert_eq!(
+            TaggedPointer::<DISCRIMINANT_BITS>::try_new(0, 0),
+            Err(TagError::Null)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(miri, ignore)]
+    fn null_pointer() {
+        TaggedPointer::<DISCRIMINANT_BITS>::new(0, 0);
     }
 }
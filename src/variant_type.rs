@@ -0,0 +1,54 @@
+use core::{alloc::Layout, any::TypeId};
+
+/// The type stored behind a [`TaggedBox`] for a single discriminant, an entry in the type table
+/// given by [`TaggableInner::variant_type`]
+///
+/// ```rust
+/// use core::alloc::Layout;
+/// use tagged_box::VariantType;
+///
+/// let variant = VariantType::of::<u32>();
+/// assert!(variant.is::<u32>());
+/// assert!(!variant.is::<i32>());
+/// assert_eq!(variant.layout(), Layout::new::<u32>());
+/// ```
+///
+/// [`TaggedBox`]: crate::TaggedBox
+/// [`TaggableInner::variant_type`]: crate::TaggableInner#variant_type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VariantType {
+    type_id: TypeId,
+    layout: Layout,
+}
+
+impl VariantType {
+    /// Creates the entry of a discriminant that stores a `U`
+    #[inline]
+    pub fn of<U: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<U>(),
+            layout: Layout::new::<U>(),
+        }
+    }
+
+    /// Returns the `TypeId` of the stored type
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the layout of the stored type. This is the layout of the type itself, the allocation
+    /// made by a [`TaggedBox`] may be aligned further to keep its lower bits free
+    ///
+    /// [`TaggedBox`]: crate::TaggedBox
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns true if the stored type is `U`
+    #[inline]
+    pub fn is<U: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<U>()
+    }
+}
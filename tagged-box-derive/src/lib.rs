@@ -0,0 +1,244 @@
+//! `#[derive(Taggable)]` for [`tagged-box`], which generates the same container, [`TaggableInner`]
+//! and [`TaggableContainer`] impls as the `tagged_box!` macro for an enum declared on its own.
+//! It's re-exported by `tagged-box` with the `derive` feature enabled
+//!
+//! ```toml
+//! tagged_box = { version = "0.1.1", features = ["derive"] }
+//! ```
+//!
+//! The container is named by `#[tagged(container = "...")]`, which takes every option
+//! `#[tagged_box(...)]` does. As a derive can't see the other traits derived on the enum, the
+//! traits derived on the container are given by `derive(...)`
+//!
+//! ```rust
+//! use tagged_box::{Taggable, TaggableContainer};
+//!
+//! #[derive(Debug, Clone, PartialEq, Taggable)]
+//! #[tagged(container = "Container", derive(Debug, Clone, PartialEq))]
+//! pub enum Item {
+//!     /// Docs and other attributes can be put on variants
+//!     Text(String),
+//!     Numbers(i32, f32),
+//!     #[tagged(inline)]
+//!     Short(i16),
+//!     Struct {
+//!         float: f32,
+//!         boolean: bool,
+//!     },
+//!     Nothing,
+//! }
+//!
+//! let container = Container::from(String::from("Hello from tagged-box!"));
+//! assert_eq!(container.clone().into_inner(), Item::Text(String::from("Hello from tagged-box!")));
+//!
+//! let container = Container::from((10i32, 10.0));
+//! assert_eq!(container.into_inner(), Item::Numbers(10, 10.0));
+//! ```
+//!
+//! The options are
+//!
+//! - `container = "Name"`: The name of the container, which has the same visibility as the enum
+//! - `derive(...)`: The traits derived on the container, which the enum has to implement as well.
+//!   Deriving `Copy` stores the container in a [`TaggedInline`], which only works when every
+//!   variant is `#[tagged(inline)]` or a unit
+//! - `free_bits = N` and `low_bits = N`: The number of upper and lower bits used for the discriminant
+//! - `allocator = "Allocator"`: The [`RawAllocator`] variants are allocated with
+//! - `shared = "Rc"` or `shared = "Arc"`: Stores variants in a [`TaggedRc`] or [`TaggedArc`]
+//! - `ref = "ItemRef"` and `mut = "ItemMut"`: Generates borrowed views of the variants
+//!
+//! The generated code refers to `tagged-box` as `::tagged_box`, so it has to be a dependency under
+//! that name. Generic enums and explicit discriminants aren't supported
+//!
+//! ```compile_fail
+//! use tagged_box::Taggable;
+//!
+//! // The discriminant stored in the pointer comes from the order of the variants
+//! #[derive(Taggable)]
+//! #[tagged(container = "Switch")]
+//! enum State {
+//!     On = 1,
+//!     Off = 0,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use tagged_box::Taggable;
+//!
+//! // `Long` is allocated on the heap, so the container can't be `Copy`
+//! #[derive(Clone, Copy, Taggable)]
+//! #[tagged(container = "Container", derive(Copy, Clone))]
+//! enum Item {
+//!     #[tagged(inline)]
+//!     Short(i16),
+//!     Long(u64),
+//! }
+//! ```
+//!
+//! [`tagged-box`]: https://docs.rs/tagged-box
+//! [`TaggableInner`]: https://docs.rs/tagged-box/*/tagged_box/trait.TaggableInner.html
+//! [`TaggableContainer`]: https://docs.rs/tagged-box/*/tagged_box/trait.TaggableContainer.html
+//! [`TaggedInline`]: https://docs.rs/tagged-box/*/tagged_box/struct.TaggedInline.html
+//! [`RawAllocator`]: https://docs.rs/tagged-box/*/tagged_box/trait.RawAllocator.html
+//! [`TaggedRc`]: https://docs.rs/tagged-box/*/tagged_box/rc/struct.TaggedRc.html
+//! [`TaggedArc`]: https://docs.rs/tagged-box/*/tagged_box/sync/struct.TaggedArc.html
+
+extern crate proc_macro;
+
+mod options;
+mod variant;
+
+use options::Options;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error};
+use variant::Variant;
+
+/// Generates a container and the [`TaggableInner`] and [`TaggableContainer`] impls for an enum,
+/// see the [crate docs] for the options it takes
+///
+/// [`TaggableInner`]: https://docs.rs/tagged-box/*/tagged_box/trait.TaggableInner.html
+/// [`TaggableContainer`]: https://docs.rs/tagged-box/*/tagged_box/trait.TaggableContainer.html
+/// [crate docs]: crate
+#[proc_macro_derive(Taggable, attributes(tagged))]
+pub fn derive_taggable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        Data::Struct(data) => {
+            return Err(Error::new(
+                data.struct_token.span,
+                "`Taggable` can only be derived for enums",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`Taggable` can only be derived for enums",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() || input.generics.where_clause.is_some() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`Taggable` can't be derived for generic enums",
+        ));
+    }
+
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Taggable` can't be derived for enums without variants",
+        ));
+    }
+
+    let options = Options::parse(input)?;
+    let variants = collect(data.variants.iter().map(Variant::parse))?;
+
+    if let Some(copy) = options.copy() {
+        if let Some(shared) = &options.shared {
+            return Err(Error::new(
+                shared.span(),
+                "Shared containers can't derive `Copy`, as their variants are reference counted",
+            ));
+        }
+
+        // Only inline and unit variants can be copied, as nothing else is stored in the pointer itself
+        collect(variants.iter().filter(|variant| variant.is_allocated()).map(|variant| {
+            let mut error = Error::new(
+                variant.ident.span(),
+                format!(
+                    "`{}` can only derive `Copy` when every variant is `#[tagged(inline)]` or a unit, \
+                     but `{}` is allocated on the heap",
+                    options.container, variant.ident,
+                ),
+            );
+            error.combine(Error::new_spanned(copy, "`Copy` is derived here"));
+
+            Err::<(), _>(error)
+        }))?;
+    }
+
+    let free_bits = options.free_bits.as_ref().map_or_else(
+        || quote!(::tagged_box::discriminant::DISCRIMINANT_BITS),
+        |bits| quote!(#bits),
+    );
+    let low_bits = options.low_bits.as_ref().map_or_else(
+        || quote!(::tagged_box::discriminant::LOW_DISCRIMINANT_BITS),
+        |bits| quote!(#bits),
+    );
+    let allocator = options.allocator.as_ref().map_or_else(
+        || quote!(::tagged_box::Global),
+        |allocator| quote!(#allocator),
+    );
+    let pointer = match (&options.shared, options.copy()) {
+        (Some(shared), _) => quote!(#shared),
+        (None, Some(_)) => quote!(TaggedInline),
+        (None, None) => quote!(TaggedBox),
+    };
+    let ref_view = options.ref_view.iter();
+    let mut_view = options.mut_view.iter();
+    let clone = if options.clone() {
+        quote!([Clone])
+    } else {
+        quote!([])
+    };
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let container = &options.container;
+    let derives = if options.derives.is_empty() {
+        TokenStream::new()
+    } else {
+        let derives = &options.derives;
+        quote!(derive(#( #derives ),*))
+    };
+    let doc = format!(" A tagged pointer holding an [`{}`]", ident);
+
+    Ok(quote! {
+        ::tagged_box::__tagged_box_parse! {
+            @generate
+            {
+                free_bits: { #free_bits },
+                low_bits: { #low_bits },
+                allocator: #allocator,
+                pointer: #pointer,
+                ref_view: [#( #ref_view )*],
+                mut_view: [#( #mut_view )*],
+                clone: #clone
+            }
+            [doc = #doc #derives]
+            #vis struct #container, #vis enum #ident {
+                #( #variants )*
+            }
+        }
+    })
+}
+
+/// Collects the results, combining every error into one so they're all reported at once
+fn collect<T>(results: impl Iterator<Item = syn::Result<T>>) -> syn::Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut errors: Option<Error> = None;
+
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(error) => match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            },
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(values),
+    }
+}
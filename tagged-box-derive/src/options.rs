@@ -0,0 +1,132 @@
+use quote::ToTokens;
+use syn::{meta::ParseNestedMeta, Attribute, DeriveInput, Error, Expr, Ident, LitStr, Path, Type};
+
+/// The options given to an enum through `#[tagged(...)]`, mirroring `#[tagged_box(...)]`
+pub(crate) struct Options {
+    /// The name of the generated container
+    pub container: Ident,
+    pub free_bits: Option<Expr>,
+    pub low_bits: Option<Expr>,
+    pub allocator: Option<Type>,
+    /// Either `TaggedRc` or `TaggedArc`, set by `shared = "Rc"` or `shared = "Arc"`
+    pub shared: Option<Ident>,
+    pub ref_view: Option<Ident>,
+    pub mut_view: Option<Ident>,
+    /// The traits derived on the container, which the enum has to implement as well
+    pub derives: Vec<Path>,
+}
+
+impl Options {
+    pub fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut container = None;
+        let mut free_bits = None;
+        let mut low_bits = None;
+        let mut allocator = None;
+        let mut shared = None;
+        let mut ref_view = None;
+        let mut mut_view = None;
+        let mut derives = Vec::new();
+
+        for attr in tagged_attrs(&input.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("container") {
+                    set(&meta, &mut container, parse_ident(&meta)?)
+                } else if meta.path.is_ident("free_bits") {
+                    set(&meta, &mut free_bits, meta.value()?.parse()?)
+                } else if meta.path.is_ident("low_bits") {
+                    set(&meta, &mut low_bits, meta.value()?.parse()?)
+                } else if meta.path.is_ident("allocator") {
+                    set(&meta, &mut allocator, meta.value()?.parse::<LitStr>()?.parse()?)
+                } else if meta.path.is_ident("shared") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let pointer = match &*lit.value() {
+                        "Rc" => Ident::new("TaggedRc", lit.span()),
+                        "Arc" => Ident::new("TaggedArc", lit.span()),
+                        _ => return Err(Error::new(lit.span(), "expected `shared = \"Rc\"` or `shared = \"Arc\"`")),
+                    };
+
+                    set(&meta, &mut shared, pointer)
+                } else if meta.path.is_ident("ref") {
+                    set(&meta, &mut ref_view, parse_ident(&meta)?)
+                } else if meta.path.is_ident("mut") {
+                    set(&meta, &mut mut_view, parse_ident(&meta)?)
+                } else if meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|derive| {
+                        derives.push(derive.path);
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error(
+                        "unknown `tagged` option, expected one of `container`, `derive`, `free_bits`, \
+                         `low_bits`, `allocator`, `shared`, `ref` or `mut`",
+                    ))
+                }
+            })?;
+        }
+
+        let container = container.ok_or_else(|| {
+            Error::new(
+                input.ident.span(),
+                format!(
+                    "`Taggable` needs a container for `{}`, add `#[tagged(container = \"...\")]`",
+                    input.ident,
+                ),
+            )
+        })?;
+
+        Ok(Self {
+            container,
+            free_bits,
+            low_bits,
+            allocator,
+            shared,
+            ref_view,
+            mut_view,
+            derives,
+        })
+    }
+
+    /// The container's `Copy` derive, which backs it with a `TaggedInline`
+    pub fn copy(&self) -> Option<&Path> {
+        self.derives.iter().find(|derive| is_trait(derive, "Copy"))
+    }
+
+    /// Whether the container derives `Clone`, which clones variants straight from their allocation
+    pub fn clone(&self) -> bool {
+        self.derives.iter().any(|derive| is_trait(derive, "Clone"))
+    }
+}
+
+/// The `#[tagged(...)]` attributes out of `attrs`
+pub(crate) fn tagged_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("tagged"))
+}
+
+fn is_trait(path: &Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+/// Parses a string literal holding an identifier, like `container = "Container"`
+fn parse_ident(meta: &ParseNestedMeta<'_>) -> syn::Result<Ident> {
+    let lit: LitStr = meta.value()?.parse()?;
+    lit.parse().map_err(|_| {
+        Error::new(
+            lit.span(),
+            format!("expected an identifier, found `{}`", lit.value()),
+        )
+    })
+}
+
+fn set<T>(meta: &ParseNestedMeta<'_>, option: &mut Option<T>, value: T) -> syn::Result<()> {
+    if option.is_some() {
+        return Err(Error::new_spanned(
+            &meta.path,
+            format!("`{}` was given more than once", meta.path.to_token_stream()),
+        ));
+    }
+
+    *option = Some(value);
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::options::tagged_attrs;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Error, Fields, Ident, Type};
+
+/// A variant of the enum, in the shape `tagged_box!` takes its variants in
+pub(crate) struct Variant {
+    pub ident: Ident,
+    pub kind: Kind,
+}
+
+pub(crate) enum Kind {
+    /// A single field tuple variant stored in the pointer, marked with `#[tagged(inline)]`
+    Inline(Type),
+    Tuple(Vec<Type>),
+    Struct(Vec<(Ident, Type)>),
+    Unit,
+}
+
+impl Variant {
+    pub fn parse(variant: &syn::Variant) -> syn::Result<Self> {
+        if let Some((eq, discriminant)) = &variant.discriminant {
+            return Err(Error::new_spanned(
+                quote!(#eq #discriminant),
+                "`Taggable` enums can't have explicit discriminants, as the discriminant stored in \
+                 the pointer is given by the order of the variants",
+            ));
+        }
+
+        let mut inline = None;
+        for attr in tagged_attrs(&variant.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("inline") {
+                    inline = Some(attr);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `tagged` option for a variant, expected `inline`"))
+                }
+            })?;
+        }
+
+        let kind = match &variant.fields {
+            Fields::Unnamed(fields) if inline.is_some() && fields.unnamed.len() == 1 => {
+                Kind::Inline(fields.unnamed[0].ty.clone())
+            }
+            _ if inline.is_some() => {
+                return Err(Error::new_spanned(
+                    inline,
+                    "only tuple variants with a single field can be stored inline",
+                ));
+            }
+
+            Fields::Unnamed(fields) => Kind::Tuple(
+                fields
+                    .unnamed
+                    .iter()
+                    .map(|field| field.ty.clone())
+                    .collect(),
+            ),
+            Fields::Named(fields) => Kind::Struct(
+                fields
+                    .named
+                    .iter()
+                    .map(|field| (field.ident.clone().unwrap(), field.ty.clone()))
+                    .collect(),
+            ),
+            Fields::Unit => Kind::Unit,
+        };
+
+        Ok(Self {
+            ident: variant.ident.clone(),
+            kind,
+        })
+    }
+
+    /// Whether the variant is allocated on the heap rather than stored in the pointer
+    pub fn is_allocated(&self) -> bool {
+        matches!(self.kind, Kind::Tuple(_) | Kind::Struct(_))
+    }
+}
+
+impl ToTokens for Variant {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+
+        tokens.extend(match &self.kind {
+            Kind::Inline(ty) => quote! { #[inline] #ident(#ty), },
+            Kind::Tuple(types) => quote! { #ident(#( #types ),*), },
+            Kind::Struct(fields) => {
+                let (names, types): (Vec<_>, Vec<_>) = fields.iter().cloned().unzip();
+                quote! { #ident { #( #names: #types ),* }, }
+            }
+            Kind::Unit => quote! { #ident, },
+        });
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    thread,
+};
+use tagged_box::{Taggable, TaggableContainer, TaggableInner, TaggedBox};
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, Taggable)]
+#[tagged(container = "Container", derive(Debug, Clone, PartialEq, PartialOrd))]
+pub enum Item {
+    /// A single element tuple variant
+    SingleTuple(usize),
+    ManyTuple(usize, usize, f32, usize),
+    #[tagged(inline)]
+    Short(i16),
+    Unit,
+    Orphan {
+        int: u32,
+        #[allow(dead_code)]
+        boolean: bool,
+    },
+    Text(String),
+}
+
+#[test]
+fn round_trip() {
+    let items = vec![
+        Item::SingleTuple(usize::MAX),
+        Item::ManyTuple(10, 20, 30.5, 40),
+        Item::Short(-16),
+        Item::Unit,
+        Item::Orphan {
+            int: 100,
+            boolean: true,
+        },
+        Item::Text(String::from("Hello from a derive")),
+    ];
+
+    for (discriminant, item) in items.into_iter().enumerate() {
+        let tagged = item.clone().into_tagged_box();
+        assert_eq!(tagged.discriminant() as usize, discriminant);
+        assert_eq!(Item::from_tagged_box(tagged), item);
+
+        let container = Container::from(item.clone());
+        assert_eq!(container.clone(), container);
+        assert_eq!(container.into_inner(), item);
+    }
+
+    assert_eq!(Container::from(10usize).into_inner(), Item::SingleTuple(10));
+    assert_eq!(
+        Container::from((1usize, 2usize, 3.0f32, 4usize)).into_inner(),
+        Item::ManyTuple(1, 2, 3.0, 4),
+    );
+    assert_eq!(Container::from(-1i16).into_inner(), Item::Short(-1));
+    assert_eq!(
+        Container::try_from(Item::Text(String::from("Fallible")))
+            .unwrap()
+            .into_inner(),
+        Item::Text(String::from("Fallible")),
+    );
+    assert!(Container::from(1usize) < Container::from(Item::Unit));
+
+    let mut container = Container::from(String::from("Mutated"));
+    container.with_mut(|item| match item {
+        Item::Text(text) => text.push_str(" in place"),
+        _ => unreachable!(),
+    });
+    assert_eq!(
+        container.into_inner(),
+        Item::Text(String::from("Mutated in place"))
+    );
+
+    let short = Item::Short(i16::MIN).into_tagged_box();
+    assert_eq!(unsafe { short.as_inline::<i16>() }, i16::MIN);
+    assert!(
+        TaggedBox::<Item>::downcast::<String>(Item::Text(String::new()).into_tagged_box()).is_ok()
+    );
+}
+
+#[test]
+fn drops_variants() {
+    let counter = Arc::new(());
+
+    #[derive(Taggable)]
+    #[tagged(container = "Holder")]
+    enum Counted {
+        Counter(Arc<()>),
+        #[allow(dead_code)]
+        Pair(Arc<()>, Arc<()>),
+    }
+
+    let holder = Holder::from(Counted::Pair(Arc::clone(&counter), Arc::clone(&counter)));
+    let single = Counted::Counter(Arc::clone(&counter)).into_tagged_box();
+    assert_eq!(Arc::strong_count(&counter), 4);
+
+    drop(holder);
+    drop(single);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn options() {
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Taggable)]
+    #[tagged(container = "Shared", derive(Debug, Clone, PartialEq, Eq, Hash))]
+    #[tagged(shared = "Arc", free_bits = 2, low_bits = 2, ref = "SharedRef")]
+    enum SharedItem {
+        Text(String),
+        Integer(i32),
+    }
+
+    let shared = Shared::from(String::from("Shared across threads"));
+    let cloned = shared.clone();
+    let text = thread::spawn(move || cloned.into_inner()).join().unwrap();
+    assert_eq!(
+        text,
+        SharedItem::Text(String::from("Shared across threads"))
+    );
+
+    match shared.as_ref() {
+        SharedRef::Text(text) => assert_eq!(text, "Shared across threads"),
+        SharedRef::Integer(_) => panic!("Expected text"),
+    }
+
+    let set: HashSet<_> = vec![shared.clone(), shared, Shared::from(10)]
+        .into_iter()
+        .collect();
+    assert_eq!(set.len(), 2);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Taggable)]
+    #[tagged(
+        container = "Copied",
+        derive(Debug, Clone, Copy, PartialEq),
+        mut = "CopiedMut"
+    )]
+    pub(crate) enum CopyItem {
+        #[tagged(inline)]
+        Integer(i32),
+        #[tagged(inline)]
+        Character(char),
+        Nothing,
+    }
+
+    let mut copied = Copied::from('c');
+    let before = copied;
+    copied.with_mut(|item| *item = CopyItem::Character('d'));
+    assert!(matches!(copied.as_mut(), CopiedMut::Character('d')));
+    assert_eq!(before.into_inner(), CopyItem::Character('c'));
+    assert_eq!(copied.into_inner(), CopyItem::Character('d'));
+    assert_eq!(
+        Copied::from(CopyItem::Nothing),
+        Copied::from(CopyItem::Nothing)
+    );
+    assert_eq!(Copied::from(5).into_inner(), CopyItem::Integer(5));
+
+    static LOG: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+    struct Logged;
+
+    unsafe impl tagged_box::RawAllocator for Logged {
+        unsafe fn alloc(layout: core::alloc::Layout) -> *mut u8 {
+            LOG.lock().unwrap().push(layout.size());
+            tagged_box::Global::alloc(layout)
+        }
+
+        unsafe fn dealloc(ptr: *mut u8, layout: core::alloc::Layout) {
+            tagged_box::Global::dealloc(ptr, layout)
+        }
+    }
+
+    #[derive(Debug, Taggable)]
+    #[tagged(container = "Allocated", allocator = "Logged")]
+    enum AllocatedItem {
+        Large([u64; 4]),
+    }
+
+    let allocated = Allocated::from([1u64; 4]);
+    assert_eq!(LOG.lock().unwrap().as_slice(), &[32]);
+    match allocated.into_inner() {
+        AllocatedItem::Large(large) => assert_eq!(large, [1; 4]),
+    }
+}
@@ -1,7 +1,9 @@
+#![allow(clippy::excessive_precision, clippy::legacy_numeric_constants)]
+
 use tagged_box::{tagged_box, TaggableContainer, TaggableInner};
 
 tagged_box! {
-    #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
     struct Container, enum Item {
         SingleTuple(usize),
         ManyTuple(usize, usize, f32, usize),
@@ -173,3 +175,684 @@ fn wrapped_refs_from_tagged_box() {
     assert_eq!(Item::from_tagged_box(big), Item::SingleTuple(10_000));
     assert_eq!(Item::from_tagged_box(small), Item::SingleTuple(100));
 }
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn custom_free_bits() {
+    use tagged_box::TaggedPointer;
+
+    tagged_box! {
+        #[tagged_box(free_bits = 16)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct WideContainer, enum WideItem {
+            Int(i32),
+            Pair(u8, u16),
+            Nothing,
+        }
+    }
+
+    let int = WideContainer::from(100i32);
+    assert_eq!(int.value.discriminant(), 0);
+    assert_eq!(int.clone().into_inner(), WideItem::Int(100));
+
+    let pair = WideContainer::from((10u8, 1000u16));
+    assert_eq!(pair.value.discriminant(), 1);
+    assert_eq!(pair.into_inner(), WideItem::Pair(10, 1000));
+
+    assert_eq!(
+        WideItem::from_tagged_box(WideItem::Nothing.into_tagged_box()),
+        WideItem::Nothing
+    );
+    assert_eq!(TaggedPointer::<16>::MAX_DISCRIMINANT, 65535);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn hybrid_bits() {
+    tagged_box! {
+        #[tagged_box(free_bits = 1, low_bits = 2)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct HybridContainer, enum HybridItem {
+            Byte(u8),
+            Short(u16),
+            Int(i32),
+            Long(i64),
+            Text(String),
+            Pair(u8, bool),
+            Nothing,
+        }
+    }
+
+    let byte = HybridContainer::from(10u8);
+    assert_eq!(byte.value.discriminant(), 0);
+    assert_eq!(byte.value.as_ptr::<u8>() as usize % 4, 0);
+    assert_eq!(byte.into_inner(), HybridItem::Byte(10));
+
+    let text = HybridContainer::from(String::from("Split across both ends"));
+    assert_eq!(text.value.discriminant(), 4);
+    assert_eq!(
+        text.clone().into_inner(),
+        HybridItem::Text(String::from("Split across both ends"))
+    );
+
+    let pair = HybridContainer::from((255u8, true));
+    assert_eq!(pair.value.discriminant(), 5);
+    assert_eq!(pair.into_inner(), HybridItem::Pair(255, true));
+
+    assert_eq!(
+        HybridContainer::from(HybridItem::Nothing).into_inner(),
+        HybridItem::Nothing
+    );
+}
+
+#[test]
+fn niche_optimization() {
+    use core::mem::size_of;
+
+    assert_eq!(size_of::<Container>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<Container>>(), size_of::<Container>());
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(size_of::<Option<Container>>(), 8);
+
+    // Unit variants use a dangling pointer, which is never null either
+    let unit = Some(Container::from(Item::Unit));
+    assert_eq!(unit.map(|unit| unit.into_inner()), Some(Item::Unit));
+}
+
+#[test]
+fn custom_allocator() {
+    use std::{alloc::Layout, cell::Cell};
+    use tagged_box::{Global, RawAllocator};
+
+    thread_local! {
+        static LIVE_ALLOCATIONS: Cell<isize> = const { Cell::new(0) };
+    }
+
+    struct Counted;
+
+    unsafe impl RawAllocator for Counted {
+        unsafe fn alloc(layout: Layout) -> *mut u8 {
+            LIVE_ALLOCATIONS.with(|live| live.set(live.get() + 1));
+            Global::alloc(layout)
+        }
+
+        unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+            LIVE_ALLOCATIONS.with(|live| live.set(live.get() - 1));
+            Global::dealloc(ptr, layout)
+        }
+    }
+
+    tagged_box! {
+        #[tagged_box(allocator = Counted)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            Text(String),
+            Numbers(usize, u8),
+            Nothing,
+        }
+    }
+
+    let live = || LIVE_ALLOCATIONS.with(Cell::get);
+
+    let text = Container::from(String::from("Hello from an arena!"));
+    let numbers = Container::from((10usize, 20u8));
+    let nothing = Container::from(Item::Nothing);
+    assert_eq!(live(), 2);
+
+    let cloned = text.clone();
+    assert_eq!(live(), 3);
+
+    assert_eq!(
+        cloned.into_inner(),
+        Item::Text(String::from("Hello from an arena!"))
+    );
+    assert_eq!(numbers.into_inner(), Item::Numbers(10, 20));
+    assert_eq!(nothing.into_inner(), Item::Nothing);
+    assert_eq!(live(), 1);
+
+    drop(text);
+    assert_eq!(live(), 0);
+}
+
+#[test]
+fn fallible_allocation() {
+    use std::{alloc::Layout, cell::Cell, rc::Rc};
+    use tagged_box::{AllocError, Global, RawAllocator};
+
+    thread_local! {
+        static OUT_OF_MEMORY: Cell<bool> = const { Cell::new(false) };
+    }
+
+    struct Limited;
+
+    unsafe impl RawAllocator for Limited {
+        unsafe fn alloc(layout: Layout) -> *mut u8 {
+            if OUT_OF_MEMORY.with(Cell::get) {
+                std::ptr::null_mut()
+            } else {
+                Global::alloc(layout)
+            }
+        }
+
+        unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+            Global::dealloc(ptr, layout)
+        }
+    }
+
+    tagged_box! {
+        #[tagged_box(allocator = Limited)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            Counted(Rc<()>),
+            Numbers(usize, u8),
+            Nothing,
+        }
+    }
+
+    let counter = Rc::new(());
+
+    let counted = Container::try_from(Item::Counted(counter.clone())).unwrap();
+    assert_eq!(counted.into_inner(), Item::Counted(counter.clone()));
+
+    OUT_OF_MEMORY.with(|oom| oom.set(true));
+
+    // The value is dropped when it can't be allocated
+    assert_eq!(
+        Container::try_from(Item::Counted(counter.clone())).map(|_| ()),
+        Err(AllocError),
+    );
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    assert_eq!(
+        Item::Numbers(10, 20).try_into_tagged_box().map(|_| ()),
+        Err(AllocError),
+    );
+    assert_eq!(
+        Item::Counted(counter.clone())
+            .try_into_tagged_box()
+            .map(|_| ()),
+        Err(AllocError),
+    );
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    // Unit variants are never allocated
+    let nothing = Container::try_from(Item::Nothing).unwrap();
+    assert_eq!(nothing.into_inner(), Item::Nothing);
+
+    OUT_OF_MEMORY.with(|oom| oom.set(false));
+}
+
+#[test]
+fn shared_containers() {
+    use std::{rc::Rc, sync::Arc};
+
+    tagged_box! {
+        #[tagged_box(shared = Rc)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct RcContainer, enum RcItem {
+            Counted(Rc<()>),
+            Numbers(usize, u8),
+            Nothing,
+        }
+    }
+
+    tagged_box! {
+        #[tagged_box(shared = Arc, low_bits = 3)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct ArcContainer, enum ArcItem {
+            Counted(Arc<()>),
+            Text(String),
+            Nothing,
+        }
+    }
+
+    assert_eq!(size_of::<RcContainer>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ArcContainer>>(), size_of::<usize>());
+
+    // Cloning a container shares the variant instead of cloning it
+    let counter = Rc::new(());
+    let counted = RcContainer::from(counter.clone());
+    let cloned = counted.clone();
+    assert_eq!(Rc::strong_count(&counter), 2);
+    assert_eq!(cloned, counted);
+
+    assert_eq!(cloned.into_inner(), RcItem::Counted(counter.clone()));
+    assert_eq!(Rc::strong_count(&counter), 2);
+    drop(counted);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    assert_eq!(RcContainer::from((10, 20)).into_inner(), RcItem::Numbers(10, 20));
+    assert_eq!(
+        RcContainer::try_from(RcItem::Nothing).unwrap().into_inner(),
+        RcItem::Nothing,
+    );
+
+    let counter = Arc::new(());
+    let counted = ArcContainer::from(ArcItem::Counted(counter.clone()));
+    let text = ArcContainer::from(String::from("Hello"));
+    let handle = std::thread::spawn(move || (counted.clone().into_inner(), text.into_inner()));
+
+    assert_eq!(
+        handle.join().unwrap(),
+        (ArcItem::Counted(counter.clone()), ArcItem::Text(String::from("Hello"))),
+    );
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn inline_variants() {
+    use std::{alloc::Layout, cell::Cell};
+    use tagged_box::{Global, RawAllocator};
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Counted;
+
+    unsafe impl RawAllocator for Counted {
+        unsafe fn alloc(layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            Global::alloc(layout)
+        }
+
+        unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+            Global::dealloc(ptr, layout)
+        }
+    }
+
+    tagged_box! {
+        #[tagged_box(allocator = Counted, low_bits = 2)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            #[inline]
+            Integer(i32),
+            #[inline]
+            Float(f32),
+            #[inline]
+            Bytes([u8; 3]),
+            #[inline]
+            Character(char),
+            Text(String),
+            Nothing,
+        }
+    }
+
+    let allocations = || ALLOCATIONS.with(Cell::get);
+
+    let values = [
+        Item::Integer(0),
+        Item::Integer(-1),
+        Item::Integer(i32::MAX),
+        Item::Float(-0.5),
+        Item::Bytes([0xFF, 0, 0xAB]),
+        Item::Character('🦀'),
+        Item::Nothing,
+    ];
+    for value in values.iter() {
+        let container = Container::from(value.clone());
+        assert_eq!(format!("{:?}", container.value), format!("{:?}", value));
+        assert_eq!(container.clone().into_inner(), *value);
+        assert_eq!(
+            Container::try_from(value.clone()).unwrap().into_inner(),
+            *value
+        );
+    }
+    assert_eq!(Container::from(5i32).into_inner(), Item::Integer(5));
+    assert_eq!(allocations(), 0);
+
+    // Other variants are still allocated
+    let text = Container::from(String::from("Not inline"));
+    assert_eq!(allocations(), 1);
+    assert_eq!(text.into_inner(), Item::Text(String::from("Not inline")));
+}
+
+#[test]
+fn borrowed_views() {
+    use std::cell::Cell;
+
+    tagged_box! {
+        #[tagged_box(ref = ItemRef, mut = ItemMut)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            Text(String),
+            Counter(Cell<u32>),
+            Pair(u8, String),
+            Orphan {
+                int: u32,
+                boolean: bool,
+            },
+            #[inline]
+            Short(i16),
+            Nothing,
+        }
+    }
+
+    let mut text = Container::from(String::from("Borrowed"));
+    match text.as_ref() {
+        ItemRef::Text(string) => {
+            // The reference points into the heap allocation instead of a copy
+            assert_eq!(string, "Borrowed");
+            assert_eq!(string as *const String, text.value.as_ptr::<String>());
+        }
+        _ => panic!("Expected a text variant"),
+    }
+    if let ItemMut::Text(string) = text.as_mut() {
+        string.push_str(" in place");
+    }
+    assert_eq!(
+        text.into_inner(),
+        Item::Text(String::from("Borrowed in place"))
+    );
+
+    // Interior mutability works through shared references
+    let counter = Container::from(Cell::new(0u32));
+    for _ in 0..3 {
+        if let ItemRef::Counter(cell) = counter.as_ref() {
+            cell.set(cell.get() + 1);
+        }
+    }
+    assert_eq!(counter.into_inner(), Item::Counter(Cell::new(3)));
+
+    let mut pair = Container::from((1u8, String::from("one")));
+    if let ItemMut::Pair(number, string) = pair.as_mut() {
+        *number += 1;
+        string.push('!');
+    }
+    assert!(matches!(pair.as_ref(), ItemRef::Pair(2, string) if string == "one!"));
+
+    let mut orphan = Container::from(Item::Orphan {
+        int: 10,
+        boolean: false,
+    });
+    if let ItemMut::Orphan { int, boolean } = orphan.as_mut() {
+        *int *= 2;
+        *boolean = true;
+    }
+    assert!(matches!(
+        orphan.as_ref(),
+        ItemRef::Orphan {
+            int: 20,
+            boolean: true
+        }
+    ));
+
+    assert!(matches!(
+        Container::from(-5i16).as_ref(),
+        ItemRef::Short(-5)
+    ));
+    assert!(matches!(
+        Container::from(Item::Nothing).as_ref(),
+        ItemRef::Nothing
+    ));
+
+    tagged_box! {
+        #[tagged_box(shared = Rc, ref = SharedRef)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct Shared, enum SharedItem {
+            Text(String),
+            Nothing,
+        }
+    }
+
+    let shared = Shared::from(String::from("Shared"));
+    let cloned = shared.clone();
+    match (shared.as_ref(), cloned.as_ref()) {
+        (SharedRef::Text(left), SharedRef::Text(right)) => {
+            assert!(std::ptr::eq(left, right))
+        }
+        _ => panic!("Expected text variants"),
+    }
+    assert!(matches!(
+        Shared::from(SharedItem::Nothing).as_ref(),
+        SharedRef::Nothing
+    ));
+}
+
+#[test]
+fn in_place_mutation() {
+    use std::{
+        alloc::Layout,
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+    };
+    use tagged_box::{Global, RawAllocator};
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+        static DEALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Counted;
+
+    unsafe impl RawAllocator for Counted {
+        unsafe fn alloc(layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            Global::alloc(layout)
+        }
+
+        unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+            DEALLOCATIONS.with(|count| count.set(count.get() + 1));
+            Global::dealloc(ptr, layout)
+        }
+    }
+
+    tagged_box! {
+        #[tagged_box(allocator = Counted)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct Container, enum Item {
+            Text(String),
+            Bytes(Vec<u8>),
+            Integer(i32),
+            #[inline]
+            Short(i16),
+            Nothing,
+        }
+    }
+
+    let counts = || {
+        (
+            ALLOCATIONS.with(Cell::get),
+            DEALLOCATIONS.with(Cell::get),
+        )
+    };
+
+    let mut container = Container::from(String::from("Mutated"));
+    assert_eq!(counts(), (1, 0));
+
+    let length = container.with_mut(|item| match item {
+        Item::Text(text) => {
+            text.push_str(" in place");
+            text.len()
+        }
+        _ => unreachable!(),
+    });
+    assert_eq!(length, 16);
+    assert_eq!(counts(), (1, 0));
+
+    // Both `String` and `Vec<u8>` have the same layout, so the allocation is kept
+    container.with_mut(|item| *item = Item::Bytes(vec![1, 2, 3]));
+    assert_eq!(counts(), (1, 0));
+    assert_eq!(container.clone().into_inner(), Item::Bytes(vec![1, 2, 3]));
+    assert_eq!(counts(), (2, 1));
+
+    // Switching to a variant with another layout frees the old allocation
+    container.with_mut(|item| *item = Item::Integer(10));
+    assert_eq!(counts(), (3, 2));
+
+    container.with_mut(|item| *item = Item::Short(-1));
+    assert_eq!(counts(), (3, 3));
+    container.with_mut(|item| *item = Item::Nothing);
+    assert_eq!(counts(), (3, 3));
+    container.with_mut(|item| *item = Item::Text(String::from("Back again")));
+    assert_eq!(counts(), (4, 3));
+
+    // The variant is stored back even when the closure panics
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        container.with_mut(|item| {
+            *item = Item::Bytes(vec![4, 5, 6]);
+            panic!("Mutation failed");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(counts(), (4, 3));
+    assert_eq!(container.into_inner(), Item::Bytes(vec![4, 5, 6]));
+    assert_eq!(counts(), (4, 4));
+}
+
+#[test]
+fn send_between_threads() {
+    use std::{sync::Arc, thread};
+
+    tagged_box! {
+        #[tagged_box(shared = Arc)]
+        #[derive(Debug, Clone, PartialEq)]
+        struct SharedContainer, enum SharedItem {
+            Text(String),
+            Integer(i32),
+        }
+    }
+
+    let container = Container::from(usize::MAX);
+    let item = thread::spawn(move || container.into_inner()).join().unwrap();
+    assert_eq!(item, Item::SingleTuple(usize::MAX));
+
+    let shared = SharedContainer::from(String::from("Shared across threads"));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || shared.into_inner())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), SharedItem::Text(String::from("Shared across threads")));
+    }
+
+    let arc = Arc::new(Container::from(10usize));
+    let cloned = Arc::clone(&arc);
+    thread::spawn(move || assert_eq!(Container::clone(&cloned).into_inner(), Item::SingleTuple(10)))
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn copy_containers() {
+    tagged_box! {
+        #[tagged_box(ref = CopyRef, mut = CopyMut)]
+        #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+        struct CopyContainer, enum CopyItem {
+            #[inline]
+            Integer(i32),
+            #[inline]
+            Character(char),
+            Nothing,
+        }
+    }
+
+    let integer = CopyContainer::from(-10);
+    let copied = integer;
+    assert_eq!(integer, copied);
+    assert_eq!(integer.into_inner(), CopyItem::Integer(-10));
+    assert_eq!(copied.into_inner(), CopyItem::Integer(-10));
+
+    let mut character = CopyContainer::try_from(CopyItem::Character('c')).unwrap();
+    let before = character;
+    character.with_mut(|item| *item = CopyItem::Nothing);
+    assert_eq!(character.into_inner(), CopyItem::Nothing);
+    assert_eq!(before.into_inner(), CopyItem::Character('c'));
+
+    match before.as_ref() {
+        CopyRef::Character(character) => assert_eq!(character, 'c'),
+        _ => panic!("Expected a character"),
+    }
+    assert!(matches!(character.as_mut(), CopyMut::Nothing));
+
+    let containers = [integer; 4];
+    assert!(containers.iter().all(|container| container.into_inner() == CopyItem::Integer(-10)));
+}
+
+#[test]
+fn hash_keys() {
+    use std::{
+        collections::{hash_map::DefaultHasher, HashMap, HashSet},
+        hash::{Hash, Hasher},
+    };
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    tagged_box! {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        struct Interned, enum Symbol {
+            Name(String),
+            Index(u32),
+            Pair(u32, char),
+            #[inline]
+            Short(i16),
+            Empty,
+        }
+    }
+
+    let name = Interned::from(String::from("interned"));
+    assert_eq!(hash_of(&name), hash_of(&name.clone()));
+    assert_eq!(hash_of(&name), hash_of(&Interned::from(String::from("interned"))));
+    assert_eq!(hash_of(&name.clone().into_inner()), hash_of(&Symbol::Name(String::from("interned"))));
+
+    let mut set = HashSet::new();
+    assert!(set.insert(name.clone()));
+    assert!(set.insert(Interned::from(10u32)));
+    assert!(set.insert(Interned::from((10u32, 'a'))));
+    assert!(set.insert(Interned::from(-10i16)));
+    assert!(set.insert(Interned::from(Symbol::Empty)));
+
+    assert!(!set.insert(Interned::from(String::from("interned"))));
+    assert!(!set.insert(Interned::from((10u32, 'a'))));
+    assert!(!set.insert(Interned::from(-10i16)));
+    assert!(!set.insert(Interned::from(Symbol::Empty)));
+    assert!(set.contains(&Interned::from(10u32)));
+    assert!(!set.contains(&Interned::from(11u32)));
+    assert_eq!(set.len(), 5);
+
+    let mut ids = HashMap::new();
+    ids.insert(name, 0);
+    ids.insert(Interned::from(String::from("other")), 1);
+    assert_eq!(ids.get(&Interned::from(String::from("interned"))), Some(&0));
+    assert_eq!(ids.get(&Interned::from(String::from("other"))), Some(&1));
+
+    tagged_box! {
+        #[tagged_box(shared = Rc)]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        struct SharedInterned, enum SharedSymbol {
+            Name(String),
+            Index(u32),
+        }
+    }
+
+    let shared = SharedInterned::from(String::from("shared"));
+    assert_eq!(hash_of(&shared), hash_of(&shared.clone()));
+    assert_eq!(hash_of(&shared), hash_of(&SharedInterned::from(String::from("shared"))));
+
+    let shared_set: HashSet<_> = vec![shared.clone(), shared, SharedInterned::from(1u32)].into_iter().collect();
+    assert_eq!(shared_set.len(), 2);
+
+    tagged_box! {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        struct CopyInterned, enum CopySymbol {
+            #[inline]
+            Index(u32),
+            Empty,
+        }
+    }
+
+    let copied: HashSet<_> = [CopyInterned::from(1u32), CopyInterned::from(1u32), CopyInterned::from(CopySymbol::Empty)]
+        .iter()
+        .copied()
+        .collect();
+    assert_eq!(copied.len(), 2);
+    assert_eq!(hash_of(&CopyInterned::from(1u32)), hash_of(&CopyInterned::from(1u32)));
+}